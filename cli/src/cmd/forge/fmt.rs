@@ -75,6 +75,8 @@ impl FmtArgs {
                 out.extend(ethers::solc::utils::source_files(path).into_iter().map(Input::Path));
             } else if path.is_sol() {
                 out.push(Input::Path(path.to_path_buf()));
+            } else if path.is_yul() {
+                warn!("Cannot process path {}: Yul files are not supported", path.display());
             } else {
                 warn!("Cannot process path {}", path.display());
             }
@@ -140,24 +142,31 @@ impl Cmd for FmtArgs {
                             diags
                         ))?;
 
-                if !parsed.invalid_inline_config_items.is_empty() {
-                    let path = match input {
-                        Input::Path(path) => {
-                            let path = path.strip_prefix(&config.__root.0).unwrap_or(path);
-                            format!("{}", path.display())
-                        }
-                        Input::Stdin(_) => "stdin".to_string()
-                    };
-                    for (loc, warning) in &parsed.invalid_inline_config_items {
-                        let mut lines = source[..loc.start().min(source.len())].split('\n');
-                        let col = lines.next_back().unwrap().len() + 1;
-                        let row = lines.count() + 1;
-                        cli_warn!("[{}:{}:{}] {}", path, row, col, warning);
+                let path = match input {
+                    Input::Path(path) => {
+                        let path = path.strip_prefix(&config.__root.0).unwrap_or(path);
+                        format!("{}", path.display())
                     }
+                    Input::Stdin(_) => "stdin".to_string()
+                };
+                let position = |offset: usize| {
+                    let mut lines = source[..offset.min(source.len())].split('\n');
+                    let col = lines.next_back().unwrap().len() + 1;
+                    let row = lines.count() + 1;
+                    (row, col)
+                };
+
+                for (loc, warning) in &parsed.invalid_inline_config_items {
+                    let (row, col) = position(loc.start());
+                    cli_warn!("[{}:{}:{}] {}", path, row, col, warning);
                 }
 
                 let mut output = String::new();
-                format(&mut output, parsed, config.fmt.clone()).unwrap();
+                let unsupported = format(&mut output, parsed, config.fmt.clone()).unwrap();
+                for node in unsupported {
+                    let (row, col) = position(node.loc.start());
+                    cli_warn!("[{}:{}:{}] {} is not supported yet, keeping it as is", path, row, col, node.kind);
+                }
 
                 solang_parser::parse(&output, 0).map_err(|diags| {
                     eyre::eyre!(
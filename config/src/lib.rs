@@ -454,6 +454,7 @@ impl Config {
     pub fn try_from<T: Provider>(provider: T) -> Result<Self, ExtractConfigError> {
         let figment = Figment::from(provider);
         let mut config = figment.extract::<Self>().map_err(|error| ExtractConfigError { error })?;
+        config.fmt.validate().map_err(|err| ExtractConfigError { error: err.into() })?;
         config.profile = figment.profile().clone();
         Ok(config)
     }
@@ -3889,6 +3890,23 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_fmt_config_contradicting_visibility() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file(
+                "foundry.toml",
+                r#"
+                [fmt]
+                omit_default_visibility = true
+                explicit_default_visibility = true
+            "#,
+            )?;
+            assert!(Config::try_from(Config::figment()).is_err());
+
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_invariant_config() {
         figment::Jail::expect_with(|jail| {
@@ -0,0 +1,313 @@
+//! Configuration specific to the `forge fmt` command and the `forge_fmt` package. Kept in
+//! a separate crate with no file system or network dependencies, so the formatter can be built
+//! for WASM targets
+
+use semver::Version;
+use serde::{Deserialize, Serialize};
+
+/// Contains the config and rule set. Missing options are set to their default values when
+/// deserializing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FormatterConfig {
+    /// Maximum line length where formatter will try to wrap the line
+    pub line_length: usize,
+    /// Number of spaces per indentation level
+    pub tab_width: usize,
+    /// Infer the number of spaces per indentation level from the source code,
+    /// falling back to `tab_width` if it can't be detected
+    pub detect_tab_width: bool,
+    /// Print spaces between brackets
+    pub bracket_spacing: bool,
+    /// Print a space inside empty blocks (`{ }`). Follows `bracket_spacing` if not set
+    pub empty_block_spacing: Option<bool>,
+    /// Style of uint/int256 types
+    pub int_types: IntTypes,
+    /// Style of multiline function header in case it doesn't fit
+    pub multiline_func_header: MultilineFuncHeaderStyle,
+    /// Style of function attributes in case they don't fit on the same line as the parameters
+    pub functions_attribute_multiline: FunctionAttributeMultilineStyle,
+    /// Style of quotation marks
+    pub quote_style: QuoteStyle,
+    /// Style of underscores in number literals
+    pub number_underscore: NumberUnderscore,
+    /// Style of single line blocks in statements
+    pub single_line_statement_blocks: SingleLineBlockStyle,
+    /// Write function bodies consisting of a single statement on a single line if they fit
+    pub single_line_function_bodies: bool,
+    /// Style of braces around single statement `if`/`else` bodies
+    pub if_statement_braces: StatementBracesStyle,
+    /// Style of braces around single statement `for`/`while`/`do-while` bodies
+    pub loop_braces: StatementBracesStyle,
+    /// Print space in variable `override` definition
+    pub variable_override_spacing: bool,
+    /// Print spaces around `=>` in mapping types
+    pub mapping_arrow_spacing: bool,
+    /// Pad struct member types to the width of the widest type in the struct
+    pub align_struct_members: bool,
+    /// Pad the left sides of consecutive assignment statements so their `=` are aligned
+    pub align_assignments: bool,
+    /// Write enum values on a single line if they fit
+    pub single_line_enums: bool,
+    /// Maximum number of blank lines preserved between statements and declarations
+    pub max_blank_lines: usize,
+    /// Separate the pragma and import directives from the following declarations with a blank line
+    pub blank_line_after_header: bool,
+    /// Path prefixes of import groups. If set, consecutive imports are split into groups by the
+    /// first matching prefix, sorted by path within each group and separated by a blank line.
+    /// Imports which don't match any prefix are put into the last group
+    pub import_groups: Vec<String>,
+    /// Reorder contract members by kind: type declarations, state variables, events, errors,
+    /// modifiers, constructor and functions. Comments are moved along with the members
+    pub sort_contract_members: bool,
+    /// Style of NatSpec comments
+    pub natspec_style: NatSpecStyle,
+    /// Copy the blocks of assembly statements as is, only shifting them to the current
+    /// indentation, instead of formatting the Yul code
+    pub passthrough_assembly: bool,
+    /// Style of line endings
+    pub line_ending: LineEnding,
+    /// Target compiler version, used to avoid syntax the compiler doesn't support
+    pub compiler_version: Option<Version>,
+    /// Omit the visibility of functions if it's the default one for the `compiler_version`.
+    /// Can't be enabled together with `explicit_default_visibility`
+    pub omit_default_visibility: bool,
+    /// Write the visibility of functions omitting it if it's the default one for the
+    /// `compiler_version`. Can't be enabled together with `omit_default_visibility`
+    pub explicit_default_visibility: bool,
+    /// Globs to ignore
+    pub ignore: Vec<String>,
+}
+
+/// Style of uint/int256 types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntTypes {
+    /// Print the explicit uint256 or int256
+    Long,
+    /// Print the implicit uint or int
+    Short,
+    /// Use the type defined in the source code
+    Preserve,
+}
+
+/// Style of underscores in number literals
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NumberUnderscore {
+    /// Remove all underscores
+    Remove,
+    /// Add an underscore every thousand, if greater than 9999
+    /// e.g. 1000 -> 1000 and 10000 -> 10_000
+    Thousands,
+    /// Use the underscores defined in the source code
+    Preserve,
+}
+
+/// Style of string quotes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuoteStyle {
+    /// Use double quotes where possible
+    Double,
+    /// Use single quotes where possible
+    Single,
+    /// Use quotation mark defined in the source code
+    Preserve,
+}
+
+impl QuoteStyle {
+    /// Get associated quotation mark with option
+    pub fn quote(self) -> Option<char> {
+        match self {
+            QuoteStyle::Double => Some('"'),
+            QuoteStyle::Single => Some('\''),
+            QuoteStyle::Preserve => None,
+        }
+    }
+}
+
+/// Style of single line blocks in statements
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SingleLineBlockStyle {
+    /// Prefer single line block when possible
+    Single,
+    /// Always use multiline block
+    Multi,
+    /// Preserve the original style
+    Preserve,
+}
+
+/// Style of braces around single statement bodies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatementBracesStyle {
+    /// Always wrap the body in braces
+    Always,
+    /// Omit the braces if the body is written on a single line
+    Preserve,
+}
+
+/// Style of NatSpec comments
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NatSpecStyle {
+    /// Use line comments (`///`)
+    Line,
+    /// Use block comments (`/** */`)
+    Block,
+    /// Use the style defined in the source code
+    Preserve,
+}
+
+/// Style of line endings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LineEnding {
+    /// Use `\n`
+    Lf,
+    /// Use `\r\n`
+    Crlf,
+    /// Use the line ending which is the most common in the source code
+    Preserve,
+}
+
+/// Style of function header in case it doesn't fit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MultilineFuncHeaderStyle {
+    /// Write function parameters multiline first
+    ParamsFirst,
+    /// Write function attributes multiline first
+    AttributesFirst,
+    /// If function params or attrs are multiline
+    /// split the rest
+    All,
+}
+
+/// Style of function attributes in case they don't fit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FunctionAttributeMultilineStyle {
+    /// Write each attribute and modifier on a separate line
+    /// and the opening brace of the body on the next line
+    Split,
+    /// Write the attributes and modifiers separated by spaces, wrapping them to the next line on
+    /// overflow, and the opening brace of the body on the next line
+    Wrap,
+}
+
+impl Default for FormatterConfig {
+    fn default() -> Self {
+        FormatterConfig {
+            line_length: 120,
+            tab_width: 4,
+            detect_tab_width: false,
+            bracket_spacing: false,
+            empty_block_spacing: None,
+            int_types: IntTypes::Long,
+            multiline_func_header: MultilineFuncHeaderStyle::AttributesFirst,
+            functions_attribute_multiline: FunctionAttributeMultilineStyle::Split,
+            quote_style: QuoteStyle::Double,
+            number_underscore: NumberUnderscore::Preserve,
+            single_line_statement_blocks: SingleLineBlockStyle::Preserve,
+            single_line_function_bodies: false,
+            if_statement_braces: StatementBracesStyle::Preserve,
+            loop_braces: StatementBracesStyle::Preserve,
+            variable_override_spacing: true,
+            mapping_arrow_spacing: true,
+            align_struct_members: false,
+            align_assignments: false,
+            single_line_enums: false,
+            max_blank_lines: 1,
+            blank_line_after_header: true,
+            import_groups: vec![],
+            sort_contract_members: false,
+            natspec_style: NatSpecStyle::Preserve,
+            passthrough_assembly: false,
+            line_ending: LineEnding::Preserve,
+            compiler_version: None,
+            omit_default_visibility: false,
+            explicit_default_visibility: false,
+            ignore: vec![],
+        }
+    }
+}
+
+impl FormatterConfig {
+    /// Check that the enabled options don't contradict each other
+    pub fn validate(&self) -> Result<(), String> {
+        if self.omit_default_visibility && self.explicit_default_visibility {
+            let options = "`omit_default_visibility` and `explicit_default_visibility`";
+            return Err(format!("{options} can't be both enabled"))
+        }
+        Ok(())
+    }
+
+    /// Returns the default config with single quotes instead of double ones
+    pub fn solhint_preset() -> Self {
+        FormatterConfig { quote_style: QuoteStyle::Single, ..FormatterConfig::default() }
+    }
+
+    /// Returns the default config with the line length of `prettier-plugin-solidity`
+    pub fn prettier_preset() -> Self {
+        FormatterConfig { line_length: 80, ..FormatterConfig::default() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_roundtrip() {
+        let config = FormatterConfig {
+            line_length: 100,
+            quote_style: QuoteStyle::Single,
+            natspec_style: NatSpecStyle::Block,
+            import_groups: vec!["forge-std/".to_string()],
+            compiler_version: Some(Version::new(0, 8, 19)),
+            ..Default::default()
+        };
+        let serialized = toml::to_string(&config).unwrap();
+        let deserialized: FormatterConfig = toml::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, config);
+
+        let partial: FormatterConfig =
+            toml::from_str("line_length = 100\nquote_style = \"single\"").unwrap();
+        assert_eq!(
+            partial,
+            FormatterConfig {
+                line_length: 100,
+                quote_style: QuoteStyle::Single,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn presets() {
+        let solhint = FormatterConfig::solhint_preset();
+        assert_eq!(solhint.quote_style, QuoteStyle::Single);
+        assert_eq!(
+            solhint,
+            FormatterConfig { quote_style: QuoteStyle::Single, ..Default::default() }
+        );
+
+        let prettier = FormatterConfig::prettier_preset();
+        assert_eq!(prettier.line_length, 80);
+        assert_eq!(prettier, FormatterConfig { line_length: 80, ..Default::default() });
+    }
+
+    #[test]
+    fn validate_default_visibility() {
+        assert!(FormatterConfig::default().validate().is_ok());
+        let config = FormatterConfig {
+            omit_default_visibility: true,
+            explicit_default_visibility: true,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+}
@@ -46,6 +46,7 @@ pub struct FormatBuffer<W: Sized> {
     base_indent_len: usize,
     tab_width: usize,
     last_char: Option<char>,
+    last_line_blank: bool,
     current_line_len: usize,
     restrict_to_single_line: bool,
     state: WriteState,
@@ -60,6 +61,7 @@ impl<W: Sized> FormatBuffer<W> {
             indents: vec![],
             current_line_len: 0,
             last_char: None,
+            last_line_blank: false,
             restrict_to_single_line: false,
             state: WriteState::default(),
         }
@@ -72,6 +74,7 @@ impl<W: Sized> FormatBuffer<W> {
         new.base_indent_len = self.total_indent_len();
         new.current_line_len = self.current_line_len();
         new.last_char = self.last_char;
+        new.last_line_blank = self.last_line_blank;
         new.restrict_to_single_line = self.restrict_to_single_line;
         new.state = match self.state {
             WriteState::WriteTokens(state) | WriteState::LineStart(state) => {
@@ -135,6 +138,11 @@ impl<W: Sized> FormatBuffer<W> {
         matches!(self.state, WriteState::LineStart(_))
     }
 
+    /// Check if the buffer is at the beginning of a new line which follows a blank line
+    pub fn is_after_blank_line(&self) -> bool {
+        self.is_beginning_of_line() && self.last_line_blank
+    }
+
     /// Start a new indent group (skips first indent)
     pub fn start_group(&mut self) {
         self.indents.push(IndentGroup { skip_line: true });
@@ -155,6 +163,7 @@ impl<W: Sized> FormatBuffer<W> {
         if comment_state == CommentState::Line {
             comment_state = CommentState::None;
         }
+        self.last_line_blank = self.current_line_len == 0;
         self.current_line_len = 0;
         self.set_last_indent_group_skipped(false);
         self.last_char = Some('\n');
@@ -411,6 +420,27 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_blank_line_tracking() -> std::fmt::Result {
+        let mut buf = FormatBuffer::new(String::new(), TAB_WIDTH);
+        buf.indent(1);
+
+        write!(buf, "first")?;
+        assert!(!buf.is_after_blank_line());
+        writeln!(buf)?;
+        assert!(!buf.is_after_blank_line());
+        writeln!(buf)?;
+        assert!(buf.is_after_blank_line());
+
+        write!(buf, "second\n\n")?;
+        assert!(buf.is_after_blank_line());
+        write!(buf, "third")?;
+        assert!(!buf.is_after_blank_line());
+        assert_eq!(buf.w, "    first\n\n    second\n\n    third");
+
+        Ok(())
+    }
+
     #[test]
     fn test_preserves_original_content_with_default_settings() -> std::fmt::Result {
         let contents = vec![
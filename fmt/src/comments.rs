@@ -173,6 +173,9 @@ impl CommentWithMetadata {
     pub fn is_before(&self, byte: usize) -> bool {
         self.loc.start() < byte
     }
+    pub fn is_spdx_license(&self) -> bool {
+        self.contents().trim_start().starts_with("SPDX-License-Identifier:")
+    }
     pub fn contents(&self) -> &str {
         match self.ty {
             CommentType::Line => self.comment.strip_prefix("//"),
@@ -250,6 +253,74 @@ impl Comments {
             .collect()
     }
 
+    /// Split off the comments that occur before the byte offset in the src into a separate list
+    pub(crate) fn split_off_before(&mut self, byte: usize) -> Self {
+        Self {
+            prefixes: Self::remove_comments_before(&mut self.prefixes, byte).into(),
+            postfixes: Self::remove_comments_before(&mut self.postfixes, byte).into(),
+        }
+    }
+
+    /// Join the runs of doc line comments (`///`) on consecutive lines into doc block comments
+    /// (`/** */`). Comments for which `is_disabled` returns `true` are left as is
+    pub(crate) fn join_doc_lines(&mut self, src: &str, is_disabled: impl Fn(Loc) -> bool) {
+        let mut runs: Vec<Vec<CommentWithMetadata>> = Vec::new();
+        for comment in self.prefixes.drain(..) {
+            let is_doc_line = comment.ty == CommentType::DocLine && !is_disabled(comment.loc);
+            let continues_run = is_doc_line &&
+                runs.last().and_then(|run| run.last()).map_or(false, |last| {
+                    let between = &src[last.loc.end()..comment.loc.start()];
+                    last.ty == CommentType::DocLine &&
+                        !is_disabled(last.loc) &&
+                        between.trim().is_empty() &&
+                        between.matches('\n').count() == 1
+                });
+            match runs.last_mut() {
+                Some(run) if continues_run => run.push(comment),
+                _ => runs.push(vec![comment]),
+            }
+        }
+
+        self.prefixes = runs
+            .into_iter()
+            .map(|mut run| {
+                let first = run.first().unwrap();
+                if first.ty != CommentType::DocLine || is_disabled(first.loc) {
+                    return run.pop().unwrap()
+                }
+                let lines = run
+                    .iter()
+                    .map(|comment| {
+                        let line = comment.contents();
+                        format!(" * {}", line.strip_prefix(' ').unwrap_or(line))
+                            .trim_end()
+                            .to_string()
+                    })
+                    .join("\n");
+                CommentWithMetadata {
+                    ty: CommentType::DocBlock,
+                    loc: first.loc.with_end(run.last().unwrap().loc.end()),
+                    comment: format!("/**\n{lines}\n */"),
+                    ..first.clone()
+                }
+            })
+            .collect();
+    }
+
+    /// Remove the first comment which satisfies the predicate
+    pub(crate) fn remove_first(
+        &mut self,
+        predicate: impl Fn(&CommentWithMetadata) -> bool,
+    ) -> Option<CommentWithMetadata> {
+        let comments = if self.prefixes.iter().any(&predicate) {
+            &mut self.prefixes
+        } else {
+            &mut self.postfixes
+        };
+        let idx = comments.iter().position(predicate)?;
+        comments.remove(idx)
+    }
+
     pub(crate) fn pop(&mut self) -> Option<CommentWithMetadata> {
         if self.iter().next()?.is_prefix() {
             self.prefixes.pop_front()
@@ -3,7 +3,7 @@
 use crate::{
     buffer::*,
     chunk::*,
-    comments::{CommentState, CommentStringExt, CommentWithMetadata, Comments},
+    comments::{CommentPosition, CommentState, CommentStringExt, CommentWithMetadata, Comments},
     macros::*,
     solang_ext::*,
     string::{QuoteState, QuotedStringExt},
@@ -11,10 +11,14 @@ use crate::{
     FormatterConfig, InlineConfig, IntTypes, NumberUnderscore,
 };
 use ethers_core::{types::H160, utils::to_checksum};
-use foundry_config::fmt::{MultilineFuncHeaderStyle, SingleLineBlockStyle};
+use forge_fmt_config::{
+    FunctionAttributeMultilineStyle, MultilineFuncHeaderStyle, NatSpecStyle, SingleLineBlockStyle,
+    StatementBracesStyle,
+};
 use itertools::{Either, Itertools};
+use semver::Version;
 use solang_parser::pt::*;
-use std::{fmt::Write, str::FromStr};
+use std::{collections::HashMap, fmt::Write, str::FromStr};
 use thiserror::Error;
 
 type Result<T, E = FormatterError> = std::result::Result<T, E>;
@@ -27,14 +31,14 @@ pub enum FormatterError {
     Fmt(#[from] std::fmt::Error),
     /// All other errors
     #[error(transparent)]
-    Custom(Box<dyn std::error::Error>),
+    Custom(Box<dyn std::error::Error + Send + Sync>),
 }
 
 impl FormatterError {
     fn fmt() -> Self {
         Self::Fmt(std::fmt::Error)
     }
-    fn custom(err: impl std::error::Error + 'static) -> Self {
+    fn custom(err: impl std::error::Error + Send + Sync + 'static) -> Self {
         Self::Custom(Box::new(err))
     }
 }
@@ -72,6 +76,19 @@ struct Context {
     contract: Option<ContractDefinition>,
     function: Option<FunctionDefinition>,
     if_stmt_single_line: Option<bool>,
+    /// Widths the left sides of aligned assignments are padded to, keyed by the assignment start
+    aligned_assignments: HashMap<usize, usize>,
+    /// Write the value type of the next visited mapping on a continuation line
+    multiline_mapping: bool,
+}
+
+/// A node which the Formatter doesn't support yet and copies from the source code as is
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsupportedNode {
+    /// Location of the node in the source code
+    pub loc: Loc,
+    /// Kind of the node, e.g. `function type`
+    pub kind: &'static str,
 }
 
 /// A Solidity formatter
@@ -83,6 +100,7 @@ pub struct Formatter<'a, W> {
     context: Context,
     comments: Comments,
     inline_config: InlineConfig,
+    unsupported: Vec<UnsupportedNode>,
 }
 
 /// An action which may be committed to a Formatter
@@ -129,10 +147,13 @@ impl<'a, W: Write> Formatter<'a, W> {
     pub fn new(
         w: &'a mut W,
         source: &'a str,
-        comments: Comments,
+        mut comments: Comments,
         inline_config: InlineConfig,
         config: FormatterConfig,
     ) -> Self {
+        if matches!(config.natspec_style, NatSpecStyle::Block) {
+            comments.join_doc_lines(source, |loc| inline_config.is_disabled(loc));
+        }
         Self {
             buf: FormatBuffer::new(w, config.tab_width),
             source,
@@ -141,9 +162,39 @@ impl<'a, W: Write> Formatter<'a, W> {
             context: Context::default(),
             comments,
             inline_config,
+            unsupported: Vec::new(),
         }
     }
 
+    /// Create a new formatter with the output indented by `level` indentation levels, as if the
+    /// formatted code was nested inside other definitions (e.g. a function inside a contract)
+    pub fn with_level(
+        w: &'a mut W,
+        source: &'a str,
+        comments: Comments,
+        inline_config: InlineConfig,
+        config: FormatterConfig,
+        level: usize,
+    ) -> Self {
+        let mut formatter = Self::new(w, source, comments, inline_config, config);
+        formatter.indent(level);
+        formatter
+    }
+
+    /// Nodes which weren't structurally formatted, but copied from the source code as is
+    pub fn unsupported_nodes(&self) -> &[UnsupportedNode] {
+        &self.unsupported
+    }
+
+    /// Visit a contract part as if it was visited while formatting the contract it belongs to
+    pub(crate) fn visit_contract_part(
+        &mut self,
+        contract: ContractDefinition,
+        part: &mut ContractPart,
+    ) -> Result<()> {
+        self.with_contract_context(contract, |fmt| part.visit(fmt))
+    }
+
     /// Get the Write interface of the current temp buffer or the underlying Write
     fn buf(&mut self) -> &mut dyn Write {
         if self.temp_bufs.is_empty() {
@@ -162,6 +213,7 @@ impl<'a, W: Write> Formatter<'a, W> {
     buf_fn! { fn current_line_len(&self) -> usize }
     buf_fn! { fn total_indent_len(&self) -> usize }
     buf_fn! { fn is_beginning_of_line(&self) -> bool }
+    buf_fn! { fn is_after_blank_line(&self) -> bool }
     buf_fn! { fn last_char(&self) -> Option<char> }
     buf_fn! { fn last_indent_group_skipped(&self) -> bool }
     buf_fn! { fn set_last_indent_group_skipped(&mut self, skip: bool) }
@@ -221,10 +273,11 @@ impl<'a, W: Write> Formatter<'a, W> {
                 .saturating_add(text.len() + space)
     }
 
-    /// Write empty brackets with respect to `config.bracket_spacing` setting:
-    /// `"{ }"` if `true`, `"{}"` if `false`
+    /// Write empty brackets with respect to `config.empty_block_spacing` setting, falling back to
+    /// `config.bracket_spacing`: `"{ }"` if `true`, `"{}"` if `false`
     fn write_empty_brackets(&mut self) -> Result<()> {
-        let brackets = if self.config.bracket_spacing { "{ }" } else { "{}" };
+        let spaced = self.config.empty_block_spacing.unwrap_or(self.config.bracket_spacing);
+        let brackets = if spaced { "{ }" } else { "{}" };
         write_chunk!(self, "{brackets}")?;
         Ok(())
     }
@@ -244,6 +297,16 @@ impl<'a, W: Write> Formatter<'a, W> {
         Ok(())
     }
 
+    /// Write a blank line to the buffer unless the buffer already ends with one, so consecutive
+    /// calls (e.g. from adjacent visitors) don't stack up blank lines
+    fn write_blank_line(&mut self) -> Result<()> {
+        self.write_whitespace_separator(true)?;
+        if !self.is_after_blank_line() {
+            writeln!(self.buf())?;
+        }
+        Ok(())
+    }
+
     /// Returns number of blank lines in source between two byte indexes
     fn blank_lines(&self, start: usize, end: usize) -> usize {
         self.source[start..end].trim_comments().matches('\n').count()
@@ -447,28 +510,39 @@ impl<'a, W: Write> Formatter<'a, W> {
                     .strip_suffix("*/")
                     .unwrap()
                     .trim();
-                let lines = content.lines();
-                writeln!(self.buf(), "/**")?;
-                for line in lines {
-                    if line.trim().starts_with('*') {
-                        let line = line.trim().trim_start_matches('*');
-                        let needs_space =
-                            line.chars().next().map_or(false, |ch| !ch.is_whitespace());
-                        writeln!(self.buf(), " *{}{}", if needs_space { " " } else { "" }, line)?;
-                    } else {
-                        let curr_indent = self.buf.current_indent_len();
+                // the contents of each line following the ` *` or `///` prefix
+                let lines = content
+                    .lines()
+                    .map(|line| {
+                        if line.trim().starts_with('*') {
+                            let line = line.trim().trim_start_matches('*');
+                            let needs_space =
+                                line.chars().next().map_or(false, |ch| !ch.is_whitespace());
+                            format!("{}{}", if needs_space { " " } else { "" }, line)
+                        } else {
+                            let curr_indent = self.buf.current_indent_len();
 
-                        let indent_whitespace_count = line
-                            .char_indices()
-                            .take_while(|(idx, ch)| ch.is_whitespace() && *idx <= curr_indent)
-                            .count();
-                        let to_skip = indent_whitespace_count -
-                            indent_whitespace_count % self.config.tab_width;
+                            let indent_whitespace_count = line
+                                .char_indices()
+                                .take_while(|(idx, ch)| ch.is_whitespace() && *idx <= curr_indent)
+                                .count();
+                            let to_skip = indent_whitespace_count -
+                                indent_whitespace_count % self.config.tab_width;
 
-                        writeln!(self.buf(), " * {}", &line[to_skip..])?;
+                            format!(" {}", &line[to_skip..])
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                if matches!(self.config.natspec_style, NatSpecStyle::Line) {
+                    let lines = lines.iter().map(|line| format!("///{line}")).join("\n");
+                    write!(self.buf(), "{}", if lines.is_empty() { "///" } else { &lines })?;
+                } else {
+                    writeln!(self.buf(), "/**")?;
+                    for line in lines {
+                        writeln!(self.buf(), " *{line}")?;
                     }
+                    write!(self.buf(), " */")?;
                 }
-                write!(self.buf(), " */")?;
             } else {
                 let mut lines = comment.comment.splitn(2, '\n');
                 write!(self.buf(), "{}", lines.next().unwrap())?;
@@ -744,6 +818,17 @@ impl<'a, W: Write> Formatter<'a, W> {
     /// Try to create a string based on a callback. If the string does not fit on a single line
     /// this will return `None`
     fn simulate_to_single_line(
+        &mut self,
+        fun: impl FnMut(&mut Self) -> Result<()>,
+    ) -> Result<Option<String>> {
+        let string = self.simulate_without_newlines(fun)?;
+        Ok(string.filter(|string| self.will_it_fit(string)))
+    }
+
+    /// Try to create a string based on a callback. If the string can't be written without
+    /// newlines this will return `None`. Unlike [`Formatter::simulate_to_single_line`] this doesn't
+    /// check whether the string fits on the current line
+    fn simulate_without_newlines(
         &mut self,
         mut fun: impl FnMut(&mut Self) -> Result<()>,
     ) -> Result<Option<String>> {
@@ -757,7 +842,7 @@ impl<'a, W: Write> Formatter<'a, W> {
             };
             Ok(())
         })?;
-        Ok(if single_line && tx.will_it_fit(&tx.buffer) { Some(tx.buffer) } else { None })
+        Ok(if single_line { Some(tx.buffer) } else { None })
     }
 
     /// Try to apply a callback to a single line. If the callback cannot be applied to a single
@@ -816,9 +901,9 @@ impl<'a, W: Write> Formatter<'a, W> {
     }
 
     /// Write each [Visitable] item on a separate line. The function will check if there are any
-    /// blank lines between each visitable statement and will apply a single blank line if there
-    /// exists any. The `needs_space` callback can force a newline and is given the last_item if
-    /// any and the next item as arguments
+    /// blank lines between each visitable statement and will preserve them up to the
+    /// `max_blank_lines` limit. The `needs_space` callback can force a newline and is given the
+    /// last_item if any and the next item as arguments
     fn write_lined_visitable<'b, I, V, F>(
         &mut self,
         loc: Loc,
@@ -903,7 +988,11 @@ impl<'a, W: Write> Formatter<'a, W> {
                     if !ignore_whitespace {
                         self.write_whitespace_separator(true)?;
                         if let Some(last_loc) = last_loc {
-                            if needs_space || self.blank_lines(last_loc.end(), loc.start()) > 1 {
+                            let blank_lines = self
+                                .blank_lines(last_loc.end(), loc.start())
+                                .saturating_sub(1)
+                                .min(self.config.max_blank_lines);
+                            for _ in 0..if needs_space { blank_lines.max(1) } else { blank_lines } {
                                 writeln!(self.buf())?;
                             }
                         }
@@ -947,6 +1036,129 @@ impl<'a, W: Write> Formatter<'a, W> {
         Ok(())
     }
 
+    /// Write consecutive import directives split into the groups configured by `import_groups`.
+    /// Imports are sorted by path within each group and the groups are separated by a blank
+    /// line. Comments before an import and on the same line after it are moved along with it
+    fn write_import_groups(&mut self, imports: &mut [SourceUnitPart]) -> Result<()> {
+        let mut imports = imports
+            .iter_mut()
+            .map(|import| {
+                let loc = import.loc();
+                let prefixes = self.comments.remove_all_comments_before(loc.start());
+                let line_end = self.find_next_line(loc.end()).unwrap_or(self.source.len());
+                let postfixes = self.comments.remove_all_comments_before(line_end);
+                let path = match import {
+                    SourceUnitPart::ImportDirective(
+                        Import::Plain(path, _) |
+                        Import::GlobalSymbol(path, _, _) |
+                        Import::Rename(path, _, _),
+                    ) => path.string.clone(),
+                    _ => String::new(),
+                };
+                let group = self
+                    .config
+                    .import_groups
+                    .iter()
+                    .position(|prefix| path.starts_with(prefix))
+                    .unwrap_or(self.config.import_groups.len());
+                (group, path, prefixes, import, postfixes)
+            })
+            .collect::<Vec<_>>();
+        imports.sort_by(|(a_group, a_path, ..), (b_group, b_path, ..)| {
+            a_group.cmp(b_group).then_with(|| a_path.cmp(b_path))
+        });
+
+        let mut last_group = None;
+        for (group, _, prefixes, import, postfixes) in imports {
+            self.write_whitespace_separator(true)?;
+            if last_group.map_or(false, |last_group| last_group != group) {
+                self.write_blank_line()?;
+            }
+            last_group = Some(group);
+            self.write_comments(&prefixes)?;
+            import.visit(self)?;
+            self.write_comments(&postfixes)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write contract parts sorted by kind: using directives, type declarations, state variables,
+    /// events, errors, modifiers, constructor, receive and fallback functions and other functions.
+    /// Parts of the same kind keep their order. Comments before a part, inside of it and on the
+    /// same line after it are moved along with it
+    fn write_sorted_contract_parts(
+        &mut self,
+        contract_loc: Loc,
+        parts: &mut [ContractPart],
+        needs_space: impl Fn(&ContractPart, &ContractPart) -> bool,
+    ) -> Result<()> {
+        let kind_order = |part: &ContractPart| match part {
+            ContractPart::Using(_) => 0,
+            ContractPart::StructDefinition(_) |
+            ContractPart::EnumDefinition(_) |
+            ContractPart::TypeDefinition(_) => 1,
+            ContractPart::VariableDefinition(_) => 2,
+            ContractPart::EventDefinition(_) => 3,
+            ContractPart::ErrorDefinition(_) => 4,
+            ContractPart::FunctionDefinition(func) => match func.ty {
+                FunctionTy::Modifier => 5,
+                FunctionTy::Constructor => 6,
+                FunctionTy::Receive => 7,
+                FunctionTy::Fallback => 8,
+                FunctionTy::Function => 9,
+            },
+            ContractPart::StraySemicolon(_) => 10,
+        };
+
+        let parts_end = parts.last().map_or(contract_loc.start(), |part| part.loc().end());
+        let next_starts = parts
+            .iter()
+            .skip(1)
+            .map(|part| part.loc().start())
+            .chain(std::iter::once(contract_loc.end()))
+            .collect::<Vec<_>>();
+        let mut parts = parts
+            .iter_mut()
+            .zip(next_starts)
+            .map(|(part, next_start)| {
+                let loc = part.loc();
+                let line_end = self.find_next_line(loc.end()).unwrap_or(self.source.len());
+                let mut comments = self.comments.split_off_before(line_end.min(next_start));
+                let prefixes = comments.remove_all_comments_before(loc.start());
+                (kind_order(part), prefixes, part, comments)
+            })
+            .collect::<Vec<_>>();
+        parts.sort_by_key(|(order, ..)| *order);
+
+        for idx in 0..parts.len() {
+            if idx > 0 {
+                self.write_whitespace_separator(true)?;
+                if needs_space(&*parts[idx - 1].2, &*parts[idx].2) {
+                    self.write_blank_line()?;
+                }
+            }
+            let (_, prefixes, part, comments) = &mut parts[idx];
+            self.write_comments(prefixes.iter())?;
+            std::mem::swap(&mut self.comments, comments);
+            part.visit(self)?;
+            let postfixes = self.comments.remove_all_comments_before(contract_loc.end());
+            std::mem::swap(&mut self.comments, comments);
+            self.write_comments(&postfixes)?;
+        }
+
+        let trailing = self.comments.remove_all_comments_before(contract_loc.end());
+        if let Some(first) = trailing.first() {
+            self.write_whitespace_separator(true)?;
+            if self.blank_lines(parts_end, first.loc.start()) > 1 {
+                self.write_blank_line()?;
+            }
+            self.write_comments(&trailing)?;
+        }
+
+        Ok(())
+    }
+
     /// Visit the right side of an assignment. The function will try to write the assignment on a
     /// single line or indented on the next line. If it can't do this it resorts to letting the
     /// expression decide how to split iself on multiple lines
@@ -1057,37 +1269,204 @@ impl<'a, W: Write> Formatter<'a, W> {
             }
         }
 
+        // collapse the block without statements regardless of the whitespace inside,
+        // unless there are comments which need to be preserved
+        let has_comments_inside =
+            self.comments.iter().next().map_or(false, |comment| comment.loc.end() < loc.end());
+        if statements.is_empty() && !has_comments_inside {
+            self.write_empty_brackets()?;
+            return Ok(false)
+        }
+
+        self.write_block(loc, statements)?;
+
+        Ok(false)
+    }
+
+    /// Write the items surrounded by curly braces, each item on a separate indented line.
+    /// Blank lines between the items are collapsed the same way as in
+    /// [`Formatter::write_lined_visitable`]
+    fn write_block<T>(&mut self, loc: Loc, items: &mut [T]) -> Result<()>
+    where
+        T: Visitable + LineOfCode,
+    {
         write_chunk!(self, "{{")?;
 
-        if let Some(statement) = statements.first() {
+        if let Some(item) = items.first() {
             self.write_whitespace_separator(true)?;
-            self.write_postfix_comments_before(LineOfCode::loc(statement).start())?;
+            self.write_postfix_comments_before(LineOfCode::loc(item).start())?;
         }
 
         self.indented(1, |fmt| {
-            fmt.write_lined_visitable(loc, statements.iter_mut(), |_, _| false)?;
+            fmt.write_lined_visitable(loc, items.iter_mut(), |_, _| false)?;
             Ok(())
         })?;
 
-        if !statements.is_empty() {
+        if !items.is_empty() {
             self.write_whitespace_separator(true)?;
         }
         write_chunk!(self, loc.end(), "}}")?;
 
-        Ok(false)
+        Ok(())
+    }
+
+    /// Write the source code of a node the Formatter doesn't support as is and report the node.
+    /// Nodes visited multiple times, e.g. while simulating, are reported once
+    fn visit_unsupported(&mut self, loc: Loc, kind: &'static str) -> Result<()> {
+        if !self.unsupported.iter().any(|node| node.loc == loc) {
+            self.unsupported.push(UnsupportedNode { loc, kind });
+        }
+        self.visit_source(loc)
+    }
+
+    /// Write the source code as is, like [`Visitor::visit_source`], but shift the lines after the
+    /// first one from the original indentation of the line the source code starts on to the
+    /// current indentation, keeping the relative indentation of the lines
+    fn visit_source_reindented(&mut self, loc: Loc) -> Result<()> {
+        let source = self.source;
+        let line_start = source[..loc.start()].rfind('\n').map_or(0, |idx| idx + 1);
+        let original_indent =
+            source[line_start..loc.start()].chars().take_while(|ch| ch.is_whitespace()).count();
+
+        let mut lines = source[loc.range()].lines();
+        write_chunk!(self, loc.start(), "{}", lines.next().unwrap_or_default())?;
+        for line in lines {
+            let indent = line
+                .char_indices()
+                .take(original_indent)
+                .take_while(|(_, ch)| ch.is_whitespace())
+                .last()
+                .map_or(0, |(idx, ch)| idx + ch.len_utf8());
+            writeln!(self.buf())?;
+            write!(self.buf(), "{}", &line[indent..])?;
+        }
+
+        let _ = self.comments.remove_all_comments_before(loc.end());
+
+        Ok(())
     }
 
     /// Visit statement as `Statement::Block`.
+    /// The braces are omitted on a single line unless the `braces` style is
+    /// [StatementBracesStyle::Always]
     fn visit_stmt_as_block(
         &mut self,
         stmt: &mut Statement,
         attempt_single_line: bool,
+        braces: StatementBracesStyle,
     ) -> Result<bool> {
+        let attempt_omit_braces = braces == StatementBracesStyle::Preserve;
         match stmt {
             Statement::Block { loc, statements, .. } => {
-                self.visit_block(*loc, statements, attempt_single_line, true)
+                if self.config.align_assignments {
+                    self.align_assignments(statements)?;
+                }
+                self.visit_block(*loc, statements, attempt_single_line, attempt_omit_braces)
+            }
+            _ => self.visit_block(
+                stmt.loc(),
+                &mut vec![stmt],
+                attempt_single_line,
+                attempt_omit_braces,
+            ),
+        }
+    }
+
+    /// Find the runs of consecutive assignment statements which fit on a single line and record
+    /// the width their left sides should be padded to, so the `=` of the assignments in a run are
+    /// aligned. Blank lines, comments and other statements break a run. A run is left as is if
+    /// any of its padded assignments wouldn't fit on a single line
+    fn align_assignments(&mut self, statements: &mut [Statement]) -> Result<()> {
+        let max_len =
+            self.config.line_length.saturating_sub(self.total_indent_len() + self.config.tab_width);
+
+        let mut runs: Vec<Vec<(usize, usize, usize)>> = Vec::new();
+        let mut last_end: Option<usize> = None;
+        for stmt in statements.iter_mut() {
+            let loc = stmt.loc();
+            let continues_run = last_end.map_or(false, |last_end| {
+                self.blank_lines(last_end, loc.start()) <= 1 &&
+                    !self.comments.iter().any(|comment| {
+                        comment.is_prefix() &&
+                            comment.loc.start() >= last_end &&
+                            comment.loc.end() <= loc.start()
+                    })
+            });
+            // the assignment start with the widths of its left and right sides
+            let widths = match stmt {
+                Statement::Expression(_, Expression::Assign(expr_loc, left, right))
+                    if !self.inline_config.is_disabled(loc) =>
+                {
+                    let left_str = self.simulate_without_newlines(|fmt| left.visit(fmt))?;
+                    let right_str = self.simulate_without_newlines(|fmt| right.visit(fmt))?;
+                    left_str.zip(right_str).map(|(left_str, right_str)| {
+                        (expr_loc.start(), left_str.trim().len(), right_str.trim().len())
+                    })
+                }
+                _ => None,
+            };
+            match widths {
+                Some(widths) => {
+                    match runs.last_mut() {
+                        Some(run) if continues_run => run.push(widths),
+                        _ => runs.push(vec![widths]),
+                    }
+                    last_end = Some(loc.end());
+                }
+                None => last_end = None,
+            }
+        }
+
+        for run in runs.into_iter().filter(|run| run.len() > 1) {
+            let width = run.iter().map(|(_, left_len, _)| *left_len).max().unwrap();
+            // `left = right;`
+            if run.iter().all(|(_, _, right_len)| width + right_len + 4 <= max_len) {
+                self.context
+                    .aligned_assignments
+                    .extend(run.into_iter().map(|(start, ..)| (start, width)));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Turn the branches of a ternary expression into chunks. A ternary in the false branch is
+    /// flattened into the chunks of its condition and branches, so a right-nested chain of
+    /// ternaries is written as a flat list of conditions instead of being nested further
+    fn ternary_branch_chunks(
+        &mut self,
+        loc: Loc,
+        first_expr: &mut Expression,
+        second_expr: &mut Expression,
+        chunks: &mut Vec<Chunk>,
+    ) -> Result<()> {
+        chunks.push(self.chunked(
+            first_expr.loc().start(),
+            Some(second_expr.loc().start()),
+            |fmt| {
+                write_chunk!(fmt, "?")?;
+                first_expr.visit(fmt)
+            },
+        )?);
+        match second_expr {
+            Expression::Ternary(loc, cond, first_expr, second_expr) => {
+                chunks.push(self.chunked(
+                    cond.loc().start(),
+                    Some(first_expr.loc().start()),
+                    |fmt| {
+                        write_chunk!(fmt, ":")?;
+                        cond.visit(fmt)
+                    },
+                )?);
+                self.ternary_branch_chunks(*loc, first_expr, second_expr, chunks)
+            }
+            _ => {
+                chunks.push(self.chunked(second_expr.loc().start(), Some(loc.end()), |fmt| {
+                    write_chunk!(fmt, ":")?;
+                    second_expr.visit(fmt)
+                })?);
+                Ok(())
             }
-            _ => self.visit_block(stmt.loc(), &mut vec![stmt], attempt_single_line, true),
         }
     }
 
@@ -1335,7 +1714,15 @@ impl<'a, W: Write> Formatter<'a, W> {
             )?;
         }
 
+        let wrap_attrs = matches!(
+            self.config.functions_attribute_multiline,
+            FunctionAttributeMultilineStyle::Wrap
+        );
         let mut write_attributes = |fmt: &mut Self, multiline: bool| -> Result<()> {
+            // wrapped attributes are written on the same line and moved to the next one on overflow
+            let wrap = multiline && wrap_attrs;
+            let multiline = multiline && !wrap;
+
             // write attributes
             if !func.attributes.is_empty() {
                 let attrs_loc = func
@@ -1348,7 +1735,9 @@ impl<'a, W: Write> Formatter<'a, W> {
                     fmt.indented(1, |fmt| fmt.visit_source(attrs_loc))?;
                 } else {
                     fmt.write_postfix_comments_before(attrs_loc.start())?;
-                    fmt.write_whitespace_separator(multiline)?;
+                    if !wrap {
+                        fmt.write_whitespace_separator(multiline)?;
+                    }
                     let attributes = fmt.items_to_chunks_sorted(
                         attrs_end,
                         func.attributes.iter_mut().map(|attr| Ok((attr.loc(), attr))),
@@ -1374,7 +1763,9 @@ impl<'a, W: Write> Formatter<'a, W> {
                         }),
                     )?;
                     fmt.write_postfix_comments_before(returns_loc.start())?;
-                    fmt.write_whitespace_separator(multiline)?;
+                    if !wrap {
+                        fmt.write_whitespace_separator(multiline)?;
+                    }
                     fmt.indented(1, |fmt| {
                         fmt.surrounded(
                             SurroundingChunk::new("returns (", Some(returns_loc.start()), None),
@@ -1411,6 +1802,7 @@ impl<'a, W: Write> Formatter<'a, W> {
         if attrs_multiline {
             write_attributes(self, true)?;
         }
+        // the body is put on the next line, so it doesn't run together with wrapped attributes
         Ok(attrs_multiline)
     }
 
@@ -1439,7 +1831,11 @@ impl<'a, W: Write> Formatter<'a, W> {
             self.find_next_in_src(cond.loc().end(), ')').unwrap_or_else(|| cond.loc().end());
         let attempt_single_line = single_line_stmt_wide &&
             self.should_attempt_block_single_line(if_branch.as_mut(), cond_close_paren_loc);
-        let if_branch_is_single_line = self.visit_stmt_as_block(if_branch, attempt_single_line)?;
+        let if_branch_is_single_line = self.visit_stmt_as_block(
+            if_branch,
+            attempt_single_line,
+            self.config.if_statement_braces,
+        )?;
         if single_line_stmt_wide && !if_branch_is_single_line {
             bail!(FormatterError::fmt())
         }
@@ -1453,8 +1849,11 @@ impl<'a, W: Write> Formatter<'a, W> {
             if let Statement::If(loc, cond, if_branch, else_branch) = else_branch.as_mut() {
                 self.visit_if(*loc, cond, if_branch, else_branch, false)?;
             } else {
-                let else_branch_is_single_line =
-                    self.visit_stmt_as_block(else_branch, if_branch_is_single_line)?;
+                let else_branch_is_single_line = self.visit_stmt_as_block(
+                    else_branch,
+                    if_branch_is_single_line,
+                    self.config.if_statement_braces,
+                )?;
                 if single_line_stmt_wide && !else_branch_is_single_line {
                     bail!(FormatterError::fmt())
                 }
@@ -1502,26 +1901,118 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
             self.source.len(),
         );
 
-        self.write_lined_visitable(
-            loc,
-            source_unit.0.iter_mut(),
-            |last_unit, unit| match last_unit {
-                SourceUnitPart::PragmaDirective(..) => {
-                    !matches!(unit, SourceUnitPart::PragmaDirective(..))
-                }
-                SourceUnitPart::ImportDirective(_) => {
-                    !matches!(unit, SourceUnitPart::ImportDirective(_))
-                }
-                SourceUnitPart::ErrorDefinition(_) => {
-                    !matches!(unit, SourceUnitPart::ErrorDefinition(_))
+        // the SPDX license identifier is moved to the first line if anything precedes it
+        let source = self.source;
+        let license = self.comments.remove_first(|comment| {
+            comment.is_spdx_license() && !source[..comment.loc.start()].trim().is_empty()
+        });
+        if let Some(license) = license {
+            let license = CommentWithMetadata {
+                position: CommentPosition::Prefix,
+                has_newline_before: false,
+                ..license
+            };
+            self.write_comment(&license, true)?;
+        }
+
+        let blank_line_after_header = self.config.blank_line_after_header;
+        let needs_space = |last_unit: &SourceUnitPart, unit: &SourceUnitPart| match last_unit {
+            SourceUnitPart::PragmaDirective(..) => match unit {
+                SourceUnitPart::PragmaDirective(..) => false,
+                SourceUnitPart::ImportDirective(_) => true,
+                _ => blank_line_after_header,
+            },
+            SourceUnitPart::ImportDirective(_) => match unit {
+                SourceUnitPart::PragmaDirective(..) => true,
+                SourceUnitPart::ImportDirective(_) => false,
+                _ => blank_line_after_header,
+            },
+            SourceUnitPart::ErrorDefinition(_) => {
+                !matches!(unit, SourceUnitPart::ErrorDefinition(_))
+            }
+            SourceUnitPart::Using(_) => !matches!(unit, SourceUnitPart::Using(_)),
+            SourceUnitPart::VariableDefinition(_) => {
+                !matches!(unit, SourceUnitPart::VariableDefinition(_))
+            }
+            _ => true,
+        };
+
+        let group_imports = !self.config.import_groups.is_empty() &&
+            !source_unit.0.iter().any(|unit| {
+                matches!(unit, SourceUnitPart::ImportDirective(_)) &&
+                    self.inline_config.is_disabled(unit.loc())
+            });
+        if !group_imports {
+            self.write_lined_visitable(loc, source_unit.0.iter_mut(), needs_space)?;
+        } else {
+            let is_import =
+                |unit: &SourceUnitPart| matches!(unit, SourceUnitPart::ImportDirective(_));
+            let mut units = &mut source_unit.0[..];
+            loop {
+                let imports_start = units.iter().position(is_import).unwrap_or(units.len());
+                let imports_len =
+                    units[imports_start..].iter().take_while(|unit| is_import(unit)).count();
+                let (before, rest) = std::mem::take(&mut units).split_at_mut(imports_start);
+                let (imports, rest) = rest.split_at_mut(imports_len);
+                units = rest;
+
+                let imports_loc = match (imports.first(), imports.last()) {
+                    (Some(first), Some(last)) => first.loc().with_end(last.loc().end()),
+                    _ => {
+                        self.write_lined_visitable(loc, before.iter_mut(), needs_space)?;
+                        break
+                    }
+                };
+
+                // the last comment before the imports is written right above them
+                let last_comment_end = self
+                    .comments
+                    .iter()
+                    .filter(|comment| comment.is_before(imports_loc.start()))
+                    .last()
+                    .map(|comment| comment.loc.end());
+                let last_unit_end = before.last().map(|unit| unit.loc().end());
+                self.write_lined_visitable(
+                    loc.with_end(imports_loc.start()),
+                    before.iter_mut(),
+                    needs_space,
+                )?;
+                if self.last_char().is_some() {
+                    self.write_whitespace_separator(true)?;
+                    let blank_line = match (last_unit_end, last_comment_end) {
+                        (Some(unit_end), Some(comment_end)) if comment_end > unit_end => {
+                            self.blank_lines(comment_end, imports_loc.start()) > 1
+                        }
+                        (None, Some(comment_end)) => {
+                            self.blank_lines(comment_end, imports_loc.start()) > 1
+                        }
+                        _ => true,
+                    };
+                    if blank_line {
+                        self.write_blank_line()?;
+                    }
                 }
-                SourceUnitPart::Using(_) => !matches!(unit, SourceUnitPart::Using(_)),
-                SourceUnitPart::VariableDefinition(_) => {
-                    !matches!(unit, SourceUnitPart::VariableDefinition(_))
+
+                self.write_import_groups(imports)?;
+
+                let next_comment_start = self
+                    .comments
+                    .iter()
+                    .next()
+                    .filter(|comment| units.first().map_or(true, |unit| comment.loc < unit.loc()))
+                    .map(|comment| comment.loc.start());
+                let blank_line = match (next_comment_start, units.first()) {
+                    (Some(comment_start), _) => {
+                        self.blank_lines(imports_loc.end(), comment_start) > 1
+                    }
+                    (None, Some(unit)) => needs_space(imports.last().unwrap(), unit),
+                    (None, None) => false,
+                };
+                if blank_line {
+                    self.write_blank_line()?;
                 }
-                _ => true,
-            },
-        )?;
+            }
+        }
 
         // EOF newline
         if self.last_char().map_or(true, |char| char != '\n') {
@@ -1583,6 +2074,14 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
                 );
             }
 
+            // collapse the body only if there are neither parts nor comments inside of it
+            let has_comments =
+                fmt.comments.iter().next().map_or(false, |c| c.is_before(contract.loc.end()));
+            if contract.parts.is_empty() && !has_comments {
+                fmt.write_empty_brackets()?;
+                return Ok(())
+            }
+
             write_chunk!(fmt, "{{")?;
 
             fmt.indented(1, |fmt| {
@@ -1593,39 +2092,43 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
                     return Ok(())
                 }
 
-                fmt.write_lined_visitable(
-                    contract.loc,
-                    contract.parts.iter_mut(),
-                    |last_part, part| match last_part {
-                        ContractPart::ErrorDefinition(_) => {
-                            !matches!(part, ContractPart::ErrorDefinition(_))
-                        }
-                        ContractPart::EventDefinition(_) => {
-                            !matches!(part, ContractPart::EventDefinition(_))
-                        }
-                        ContractPart::VariableDefinition(_) => {
-                            !matches!(part, ContractPart::VariableDefinition(_))
-                        }
-                        ContractPart::TypeDefinition(_) => {
-                            !matches!(part, ContractPart::TypeDefinition(_))
-                        }
-                        ContractPart::EnumDefinition(_) => {
-                            !matches!(part, ContractPart::EnumDefinition(_))
-                        }
-                        ContractPart::Using(_) => !matches!(part, ContractPart::Using(_)),
-                        ContractPart::FunctionDefinition(last_def) => {
-                            if last_def.is_empty() {
-                                match part {
-                                    ContractPart::FunctionDefinition(def) => !def.is_empty(),
-                                    _ => true,
-                                }
-                            } else {
-                                true
+                let needs_space = |last_part: &ContractPart, part: &ContractPart| match last_part {
+                    ContractPart::ErrorDefinition(_) => {
+                        !matches!(part, ContractPart::ErrorDefinition(_))
+                    }
+                    ContractPart::EventDefinition(_) => {
+                        !matches!(part, ContractPart::EventDefinition(_))
+                    }
+                    ContractPart::VariableDefinition(_) => {
+                        !matches!(part, ContractPart::VariableDefinition(_))
+                    }
+                    ContractPart::TypeDefinition(_) => {
+                        !matches!(part, ContractPart::TypeDefinition(_))
+                    }
+                    ContractPart::EnumDefinition(_) => {
+                        !matches!(part, ContractPart::EnumDefinition(_))
+                    }
+                    ContractPart::Using(_) => !matches!(part, ContractPart::Using(_)),
+                    ContractPart::FunctionDefinition(last_def) => {
+                        if last_def.is_empty() {
+                            match part {
+                                ContractPart::FunctionDefinition(def) => !def.is_empty(),
+                                _ => true,
                             }
+                        } else {
+                            true
                         }
-                        _ => true,
-                    },
-                )
+                    }
+                    _ => true,
+                };
+
+                let sort_parts = fmt.config.sort_contract_members &&
+                    !contract.parts.iter().any(|part| fmt.inline_config.is_disabled(part.loc()));
+                if sort_parts {
+                    fmt.write_sorted_contract_parts(contract.loc, &mut contract.parts, needs_space)
+                } else {
+                    fmt.write_lined_visitable(contract.loc, contract.parts.iter_mut(), needs_space)
+                }
             })?;
 
             if !contract.parts.is_empty() {
@@ -1647,18 +2150,12 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
     ) -> Result<()> {
         return_source_if_disabled!(self, loc, ';');
 
-        #[allow(clippy::if_same_then_else)]
-        let pragma_descriptor = if ident.name == "solidity" {
-            // There are some issues with parsing Solidity's versions with crates like `semver`:
-            // 1. Ranges like `>=0.4.21<0.6.0` or `>=0.4.21 <0.6.0` are not parseable at all.
-            // 2. Versions like `0.8.10` got transformed into `^0.8.10` which is not the same.
-            // TODO: semver-solidity crate :D
-            &string.string
-        } else {
-            &string.string
-        };
-
-        write_chunk!(self, string.loc.end(), "pragma {} {};", &ident.name, pragma_descriptor)?;
+        // There are some issues with parsing Solidity's versions with crates like `semver`:
+        // 1. Ranges like `>=0.4.21<0.6.0` or `>=0.4.21 <0.6.0` are not parseable at all.
+        // 2. Versions like `0.8.10` got transformed into `^0.8.10` which is not the same.
+        // TODO: semver-solidity crate :D
+        // So `solidity` versions, as well as any other pragma values, are written verbatim
+        write_chunk!(self, string.loc.end(), "pragma {} {};", &ident.name, &string.string)?;
 
         Ok(())
     }
@@ -1705,7 +2202,9 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
         if imports.is_empty() {
             self.grouped(|fmt| {
                 write_chunk!(fmt, loc.start(), "import")?;
-                fmt.write_empty_brackets()?;
+                // the import braces aren't a block, so only `bracket_spacing` applies
+                let brackets = if fmt.config.bracket_spacing { "{ }" } else { "{}" };
+                write_chunk!(fmt, "{brackets}")?;
                 write_chunk!(fmt, loc.start(), from.loc.start(), "from")?;
                 fmt.write_quoted_str(from.loc, None, &from.string)?;
                 fmt.write_semicolon()?;
@@ -1714,6 +2213,25 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
             return Ok(())
         }
 
+        // compilers prior to 0.7.4 fail to parse import directives spanning multiple lines
+        if self.config.compiler_version.as_ref().map_or(false, |v| *v < Version::new(0, 7, 4)) {
+            let imports = imports
+                .iter()
+                .map(|(ident, alias)| match alias {
+                    Some(alias) => format!("{} as {}", ident.name, alias.name),
+                    None => ident.name.clone(),
+                })
+                .join(", ");
+            let imports = if self.config.bracket_spacing {
+                format!("{{ {imports} }}")
+            } else {
+                format!("{{{imports}}}")
+            };
+            let from = self.quote_str(from.loc, None, &from.string);
+            write_chunk!(self, loc.start(), loc.end(), "import {imports} from {from};")?;
+            return Ok(())
+        }
+
         let imports_start = imports.first().unwrap().0.loc.start();
 
         write_chunk!(self, loc.start(), imports_start, "import")?;
@@ -1783,12 +2301,13 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
                     None,
                 ),
                 SurroundingChunk::new("}", None, Some(enumeration.loc.end())),
-                |fmt, _multiline| {
+                |fmt, multiline| {
                     let values = fmt.items_to_chunks(
                         Some(enumeration.loc.end()),
                         enumeration.values.iter_mut().map(|ident| Ok((ident.loc, ident))),
                     )?;
-                    fmt.write_chunks_separated(&values, ",", true)?;
+                    let multiline = multiline || !fmt.config.single_line_enums;
+                    fmt.write_chunks_separated(&values, ",", multiline)?;
                     Ok(())
                 },
             )?;
@@ -1822,17 +2341,46 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
                     }
                 }
                 Type::Mapping(loc, from, to) => {
-                    write_chunk!(self, loc.start(), "mapping(")?;
                     let arrow_loc = self.find_next_str_in_src(loc.start(), "=>");
-                    let key_chunk = self.visit_to_chunk(from.loc().start(), arrow_loc, from)?;
-                    self.write_chunk(&key_chunk)?;
-                    write!(self.buf(), " => ")?;
                     let close_paren_loc = self.find_next_in_src(to.loc().end(), ')');
-                    let value_chunk = self.visit_to_chunk(to.loc().start(), close_paren_loc, to)?;
-                    self.write_chunk(&value_chunk)?;
-                    write!(self.buf(), ")")?;
+                    let arrow = if self.config.mapping_arrow_spacing { " => " } else { "=>" };
+
+                    let mut write_mapping = |fmt: &mut Self, multiline: bool| -> Result<()> {
+                        write_chunk!(fmt, loc.start(), "mapping(")?;
+                        let key_chunk = fmt.visit_to_chunk(from.loc().start(), arrow_loc, from)?;
+                        fmt.write_chunk(&key_chunk)?;
+                        // the value type is written on an indented continuation line, so nested
+                        // mappings decide whether to wrap at their actual position
+                        let mut write_value = |fmt: &mut Self| -> Result<()> {
+                            let mut value_chunk =
+                                fmt.visit_to_chunk(to.loc().start(), close_paren_loc, to)?;
+                            if value_chunk.prefixes.is_empty() &&
+                                value_chunk.postfixes_before.is_empty()
+                            {
+                                value_chunk.needs_space = Some(false);
+                            }
+                            fmt.write_chunk(&value_chunk)
+                        };
+                        if multiline {
+                            write!(fmt.buf(), "{}", arrow.trim_end())?;
+                            fmt.indented(1, |fmt| {
+                                writeln!(fmt.buf())?;
+                                write_value(fmt)
+                            })?;
+                        } else {
+                            write!(fmt.buf(), "{arrow}")?;
+                            write_value(fmt)?;
+                        }
+                        write!(fmt.buf(), ")")?;
+                        Ok(())
+                    };
+
+                    let multiline = std::mem::take(&mut self.context.multiline_mapping);
+                    if multiline || !self.try_on_single_line(|fmt| write_mapping(fmt, false))? {
+                        write_mapping(self, true)?;
+                    }
                 }
-                Type::Function { .. } => self.visit_source(*loc)?,
+                Type::Function { .. } => self.visit_unsupported(*loc, "function type")?,
             },
             Expression::BoolLiteral(loc, val) => {
                 write_chunk!(self, loc.start(), loc.end(), "{val}")?;
@@ -1842,7 +2390,10 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
             }
             Expression::HexNumberLiteral(loc, val) => {
                 // ref: https://docs.soliditylang.org/en/latest/types.html?highlight=address%20literal#address-literals
-                let val = if val.len() == 42 {
+                // Only address literals are checksummed, the casing of other hex numbers is kept
+                let is_address =
+                    val.len() == 42 && val[2..].chars().all(|ch| ch.is_ascii_hexdigit());
+                let val = if is_address {
                     to_checksum(&H160::from_str(val).expect(""), None)
                 } else {
                     val.to_owned()
@@ -2011,32 +2562,27 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
             Expression::AssignMultiply(..) |
             Expression::AssignDivide(..) |
             Expression::AssignModulo(..) => {
+                let align_width =
+                    self.context.aligned_assignments.get(&expr.loc().start()).copied();
                 let op = expr.operator().unwrap();
                 let (left, right) = expr.into_components();
                 let (left, right) = (left.unwrap(), right.unwrap());
 
                 left.visit(self)?;
+                if let Some(width) = align_width {
+                    let padding = width.saturating_sub(self.current_line_len());
+                    if padding > 0 {
+                        write!(self.buf(), "{}", " ".repeat(padding + 1))?;
+                    }
+                }
                 write_chunk!(self, "{op}")?;
                 self.visit_assignment(right)?;
             }
             Expression::Ternary(loc, cond, first_expr, second_expr) => {
                 cond.visit(self)?;
 
-                let first_expr = self.chunked(
-                    first_expr.loc().start(),
-                    Some(second_expr.loc().start()),
-                    |fmt| {
-                        write_chunk!(fmt, "?")?;
-                        first_expr.visit(fmt)
-                    },
-                )?;
-                let second_expr =
-                    self.chunked(second_expr.loc().start(), Some(loc.end()), |fmt| {
-                        write_chunk!(fmt, ":")?;
-                        second_expr.visit(fmt)
-                    })?;
-
-                let chunks = vec![first_expr, second_expr];
+                let mut chunks = vec![];
+                self.ternary_branch_chunks(*loc, first_expr, second_expr, &mut chunks)?;
                 if !self.try_on_single_line(|fmt| fmt.write_chunks_separated(&chunks, "", false))? {
                     self.grouped(|fmt| fmt.write_chunks_separated(&chunks, "", true))?;
                 }
@@ -2063,18 +2609,15 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
                         items.first().map(|item| item.0.start()),
                     ),
                     SurroundingChunk::new(")", None, Some(loc.end())),
-                    |fmt, _| {
+                    |fmt, multiline| {
+                        // empty slots are kept as empty chunks, so `(, b)` stays a tuple
                         let items = fmt.items_to_chunks(
                             Some(loc.end()),
                             items.iter_mut().map(|item| Ok((item.0, &mut item.1))),
                         )?;
-                        let write_items = |fmt: &mut Self, multiline| {
-                            fmt.write_chunks_separated(&items, ",", multiline)
-                        };
-                        if !fmt.try_on_single_line(|fmt| write_items(fmt, false))? {
-                            write_items(fmt, true)?;
-                        }
-                        Ok(())
+                        let multiline =
+                            multiline && fmt.are_chunks_separated_multiline("{})", &items, ",")?;
+                        fmt.write_chunks_separated(&items, ",", multiline)
                     },
                 )?;
             }
@@ -2092,7 +2635,15 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
                 expr.visit(self)?;
                 stmt.visit(self)?;
             }
-            _ => self.visit_source(loc)?,
+            Expression::New(_, expr) => {
+                write_chunk!(self, loc.start(), expr.loc().start(), "new")?;
+                expr.visit(self)?;
+            }
+            Expression::Delete(_, expr) => {
+                write_chunk!(self, loc.start(), expr.loc().start(), "delete")?;
+                expr.visit(self)?;
+            }
+            _ => self.visit_unsupported(loc, "expression")?,
         };
 
         Ok(())
@@ -2185,6 +2736,56 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
             return_source_if_disabled!(self, func.loc(), ';');
         }
 
+        // functions outside of interfaces are public by default before 0.5.0
+        let is_interface = self
+            .context
+            .contract
+            .as_ref()
+            .map_or(false, |contract| matches!(contract.ty, ContractTy::Interface(_)));
+        let public_by_default =
+            self.config.compiler_version.as_ref().map_or(false, |v| *v < Version::new(0, 5, 0));
+        if self.config.omit_default_visibility && public_by_default && !is_interface {
+            func.attributes.retain(|attr| {
+                !matches!(attr, FunctionAttribute::Visibility(Visibility::Public(_)))
+            });
+        } else if self.config.explicit_default_visibility &&
+            public_by_default &&
+            !is_interface &&
+            matches!(
+                func.ty,
+                FunctionTy::Function | FunctionTy::Constructor | FunctionTy::Fallback
+            ) &&
+            !func.attributes.iter().any(|attr| matches!(attr, FunctionAttribute::Visibility(_)))
+        {
+            // the visibility is written before the other attributes
+            let start = func
+                .attributes
+                .first()
+                .map(|attr| attr.loc())
+                .or_else(|| func.returns.first().map(|(loc, _)| *loc))
+                .or_else(|| func.body.as_ref().map(LineOfCode::loc))
+                .unwrap_or(func.loc)
+                .start();
+            let loc = Loc::File(func.loc.file_no(), start, start);
+            func.attributes.insert(0, FunctionAttribute::Visibility(Visibility::Public(Some(loc))));
+        }
+
+        // constructors can't have visibility since 0.7.0
+        let visibility_ignored =
+            self.config.compiler_version.as_ref().map_or(false, |v| *v >= Version::new(0, 7, 0));
+        if visibility_ignored && matches!(func.ty, FunctionTy::Constructor) {
+            func.attributes.retain(|attr| !matches!(attr, FunctionAttribute::Visibility(_)));
+        }
+
+        // keep only the first mutability if the function lists several of them
+        let mut has_mutability = false;
+        func.attributes.retain(|attr| {
+            let is_mutability = matches!(attr, FunctionAttribute::Mutability(_));
+            let retain = !(is_mutability && has_mutability);
+            has_mutability |= is_mutability;
+            retain
+        });
+
         self.with_function_context(func.clone(), |fmt| {
             fmt.write_postfix_comments_before(func.loc.start())?;
             fmt.write_prefix_comments_before(func.loc.start())?;
@@ -2200,7 +2801,20 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
             }
 
             // write function body
+            let body_has_comments = body_loc.map_or(false, |loc| {
+                fmt.comments.iter().next().map_or(false, |comment| comment.loc.start() < loc.end())
+            });
             match &mut func.body {
+                Some(Statement::Block { loc, unchecked: false, statements })
+                    if fmt.config.single_line_function_bodies &&
+                        statements.len() == 1 &&
+                        !body_has_comments =>
+                {
+                    fmt.write_whitespace_separator(
+                        attrs_multiline && !(func.attributes.is_empty() && func.returns.is_empty()),
+                    )?;
+                    fmt.visit_block(*loc, statements, true, false)?;
+                }
                 Some(body) => {
                     let body_loc = body_loc.unwrap();
                     let byte_offset = body_loc.start();
@@ -2341,10 +2955,32 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
                 SurroundingChunk::new("", Some(structure.name.loc.end()), None),
                 SurroundingChunk::new("}", None, Some(structure.loc.end())),
                 |fmt, _multiline| {
-                    let chunks = fmt.items_to_chunks(
+                    let mut chunks = fmt.items_to_chunks(
                         Some(structure.loc.end()),
                         structure.fields.iter_mut().map(|ident| Ok((ident.loc, ident))),
                     )?;
+                    if fmt.config.align_struct_members {
+                        // members with multiline types are left as is
+                        let types = chunks
+                            .iter()
+                            .zip(&structure.fields)
+                            .map(|(chunk, field)| {
+                                chunk
+                                    .content
+                                    .strip_suffix(field.name.name.as_str())
+                                    .filter(|ty| !ty.contains('\n'))
+                                    .map(|ty| ty.trim_end().to_string())
+                            })
+                            .collect::<Vec<_>>();
+                        let width = types.iter().flatten().map(|ty| ty.len()).max().unwrap_or(0);
+                        for ((chunk, field), ty) in
+                            chunks.iter_mut().zip(&structure.fields).zip(types)
+                        {
+                            if let Some(ty) = ty {
+                                chunk.content = format!("{ty:width$} {}", field.name.name);
+                            }
+                        }
+                    }
                     for mut chunk in chunks {
                         chunk.content.push(';');
                         fmt.write_chunk(&chunk)?;
@@ -2360,6 +2996,19 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
 
     fn visit_type_definition(&mut self, def: &mut TypeDefinition) -> Result<()> {
         return_source_if_disabled!(self, def.loc, ';');
+
+        // user-defined value types are only supported since 0.8.8, so the definition is kept as
+        // is for the older compilers
+        if self.config.compiler_version.as_ref().map_or(false, |v| *v < Version::new(0, 8, 8)) {
+            let mut loc = def.loc;
+            let has_semicolon = self.extend_loc_until(&mut loc, ';');
+            self.visit_source(loc)?;
+            if !has_semicolon {
+                self.write_semicolon()?;
+            }
+            return Ok(())
+        }
+
         self.grouped(|fmt| {
             write_chunk!(fmt, def.loc.start(), def.name.loc.start(), "type")?;
             def.name.visit(fmt)?;
@@ -2387,6 +3036,9 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
             write_chunk!(self, loc.start(), "unchecked ")?;
         }
 
+        if self.config.align_assignments {
+            self.align_assignments(statements)?;
+        }
         self.visit_block(loc, statements, false, false)?;
         Ok(())
     }
@@ -2606,35 +3258,48 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
     fn visit_var_definition(&mut self, var: &mut VariableDefinition) -> Result<()> {
         return_source_if_disabled!(self, var.loc, ';');
 
-        var.ty.visit(self)?;
+        let is_mapping = matches!(var.ty, Expression::Type(_, Type::Mapping(..)));
+        let mut write_definition = |fmt: &mut Self| -> Result<()> {
+            var.ty.visit(fmt)?;
 
-        let multiline = self.grouped(|fmt| {
-            let name_start = var.name.loc.start();
+            let multiline = fmt.grouped(|fmt| {
+                let name_start = var.name.loc.start();
 
-            let attrs = fmt.items_to_chunks_sorted(
-                Some(name_start),
-                var.attrs.iter_mut().map(|attr| Ok((attr.loc(), attr))),
-            )?;
-            if !fmt.try_on_single_line(|fmt| fmt.write_chunks_separated(&attrs, "", false))? {
-                fmt.write_chunks_separated(&attrs, "", true)?;
-            }
+                let attrs = fmt.items_to_chunks_sorted(
+                    Some(name_start),
+                    var.attrs.iter_mut().map(|attr| Ok((attr.loc(), attr))),
+                )?;
+                if !fmt.try_on_single_line(|fmt| fmt.write_chunks_separated(&attrs, "", false))? {
+                    fmt.write_chunks_separated(&attrs, "", true)?;
+                }
 
-            let mut name =
-                fmt.visit_to_chunk(name_start, Some(var.name.loc.end()), &mut var.name)?;
-            if var.initializer.is_some() {
-                name.content.push_str(" =");
-            }
-            fmt.write_chunk(&name)?;
+                let mut name =
+                    fmt.visit_to_chunk(name_start, Some(var.name.loc.end()), &mut var.name)?;
+                if var.initializer.is_some() {
+                    name.content.push_str(" =");
+                }
+                fmt.write_chunk(&name)?;
 
-            Ok(())
-        })?;
+                Ok(())
+            })?;
 
-        var.initializer
-            .as_mut()
-            .map(|init| self.indented_if(multiline, 1, |fmt| fmt.visit_assignment(init)))
-            .transpose()?;
+            var.initializer
+                .as_mut()
+                .map(|init| fmt.indented_if(multiline, 1, |fmt| fmt.visit_assignment(init)))
+                .transpose()?;
 
-        self.write_semicolon()?;
+            fmt.write_semicolon()
+        };
+
+        if is_mapping && self.try_on_single_line(|fmt| write_definition(fmt))? {
+            return Ok(())
+        }
+
+        // the value type of a mapping is wrapped if the definition doesn't fit on a single line,
+        // even if the type itself does
+        self.context.multiline_mapping = is_mapping;
+        write_definition(self)?;
+        self.context.multiline_mapping = false;
 
         Ok(())
     }
@@ -2710,7 +3375,7 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
                         .as_mut()
                         .map(|stmt| {
                             match **stmt {
-                                Statement::VariableDefinition(_, ref mut decl, ref mut expr) => {
+                                Statement::VariableDefinition(loc, ref mut decl, ref mut expr) => {
                                     fmt.visit_var_definition_stmt(loc, decl, expr, false)
                                 }
                                 Statement::Expression(loc, ref mut expr) => {
@@ -2731,7 +3396,17 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
         )?;
         match body {
             Some(body) => {
-                self.visit_stmt_as_block(body, false)?;
+                let header_end = update
+                    .as_ref()
+                    .map(|stmt| stmt.loc().end())
+                    .or_else(|| cond.as_ref().map(|expr| expr.loc().end()))
+                    .or_else(|| init.as_ref().map(|stmt| stmt.loc().end()))
+                    .unwrap_or_else(|| loc.start());
+                let close_paren_loc = self.find_next_in_src(header_end, ')').unwrap_or(header_end);
+                let preserve_braces = self.config.loop_braces == StatementBracesStyle::Preserve;
+                let attempt_single_line =
+                    preserve_braces && self.should_attempt_block_single_line(body, close_paren_loc);
+                self.visit_stmt_as_block(body, attempt_single_line, self.config.loop_braces)?;
             }
             None => {
                 self.write_empty_brackets()?;
@@ -2758,8 +3433,9 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
 
         let cond_close_paren_loc =
             self.find_next_in_src(cond.loc().end(), ')').unwrap_or_else(|| cond.loc().end());
-        let attempt_single_line = self.should_attempt_block_single_line(body, cond_close_paren_loc);
-        self.visit_stmt_as_block(body, attempt_single_line)?;
+        let attempt_single_line = self.config.loop_braces == StatementBracesStyle::Preserve &&
+            self.should_attempt_block_single_line(body, cond_close_paren_loc);
+        self.visit_stmt_as_block(body, attempt_single_line, self.config.loop_braces)?;
         Ok(())
     }
 
@@ -2771,7 +3447,7 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
     ) -> Result<(), Self::Error> {
         return_source_if_disabled!(self, loc, ';');
         write_chunk!(self, loc.start(), "do ")?;
-        self.visit_stmt_as_block(body, false)?;
+        self.visit_stmt_as_block(body, false, self.config.loop_braces)?;
         visit_source_if_disabled_else!(self, loc.with_start(body.loc().end()), {
             self.surrounded(
                 SurroundingChunk::new("while (", Some(cond.loc().start()), None),
@@ -2792,7 +3468,8 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
     ) -> Result<(), Self::Error> {
         return_source_if_disabled!(self, loc);
 
-        if !is_first_stmt {
+        // the braced branches are always written as multiline blocks
+        if !is_first_stmt || self.config.if_statement_braces == StatementBracesStyle::Always {
             self.write_if_stmt(loc, cond, if_branch, else_branch)?;
             return Ok(())
         }
@@ -2841,16 +3518,23 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
 
         if let Some(first) = chunks.first_mut() {
             if first.prefixes.is_empty() && first.postfixes_before.is_empty() {
-                first.needs_space = Some(false);
+                first.needs_space = Some(self.config.bracket_spacing);
             }
         }
-        let multiline = self.are_chunks_separated_multiline("{}}", &chunks, ",")?;
+        let format_string = if self.config.bracket_spacing { "{} }" } else { "{}}" };
+        let multiline = self.are_chunks_separated_multiline(format_string, &chunks, ",")?;
         self.indented_if(multiline, 1, |fmt| fmt.write_chunks_separated(&chunks, ",", multiline))?;
 
         let prefix = if multiline && !self.is_beginning_of_line() { "\n" } else { "" };
         let closing_bracket = format!("{}{}", prefix, "}");
         let closing_bracket_loc = args.last().unwrap().loc.end();
-        write_chunk_spaced!(self, closing_bracket_loc, Some(false), "{closing_bracket}")?;
+        let closing_bracket_spaced = !multiline && self.config.bracket_spacing;
+        write_chunk_spaced!(
+            self,
+            closing_bracket_loc,
+            Some(closing_bracket_spaced),
+            "{closing_bracket}"
+        )?;
 
         Ok(())
     }
@@ -3075,7 +3759,7 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
 
         write_chunk!(self, loc.start(), "assembly")?;
         if let Some(StringLiteral { loc, string, .. }) = dialect {
-            write_chunk!(self, loc.start(), loc.end(), "\"{string}\"")?;
+            self.write_quoted_str(*loc, None, string)?;
         }
         if let Some(flags) = flags {
             if !flags.is_empty() {
@@ -3093,7 +3777,8 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
                                 flag.loc.start(),
                                 next_byte_offset,
                                 |fmt| {
-                                    write!(fmt.buf(), "\"{}\"", flag.string)?;
+                                    let flag = fmt.quote_str(flag.loc, None, &flag.string);
+                                    write!(fmt.buf(), "{flag}")?;
                                     Ok(())
                                 },
                             )?);
@@ -3105,6 +3790,9 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
             }
         }
 
+        if self.config.passthrough_assembly {
+            return self.visit_source_reindented(block.loc)
+        }
         block.visit(self)
     }
 
@@ -3162,30 +3850,25 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
             YulExpression::HexNumberLiteral(loc, val, ident) => {
                 self.visit_yul_string_with_ident(*loc, val, ident)
             }
-            YulExpression::HexStringLiteral(val, ident) => self.visit_yul_string_with_ident(
-                val.loc,
-                &self.quote_str(val.loc, Some("hex"), &val.hex),
-                ident,
-            ),
+            // Yul string literals are written as is without applying the `quote_style`
+            YulExpression::HexStringLiteral(val, ident) => {
+                let source = self.source;
+                self.visit_yul_string_with_ident(val.loc, &source[val.loc.range()], ident)
+            }
             YulExpression::NumberLiteral(loc, val, expr, ident) => {
                 let val = if expr.is_empty() { val.to_owned() } else { format!("{val}e{expr}") };
                 self.visit_yul_string_with_ident(*loc, &val, ident)
             }
-            YulExpression::StringLiteral(val, ident) => self.visit_yul_string_with_ident(
-                val.loc,
-                &self.quote_str(val.loc, None, &val.string),
-                ident,
-            ),
+            YulExpression::StringLiteral(val, ident) => {
+                let source = self.source;
+                self.visit_yul_string_with_ident(val.loc, &source[val.loc.range()], ident)
+            }
             YulExpression::SuffixAccess(_, expr, ident) => {
-                self.visit_member_access(expr, ident, |fmt, expr| match expr.as_mut() {
-                    YulExpression::SuffixAccess(_, inner_expr, inner_ident) => {
-                        Ok(Some((inner_expr, inner_ident)))
-                    }
-                    expr => {
-                        expr.visit(fmt)?;
-                        Ok(None)
-                    }
-                })
+                // suffixes like `x.slot` and `x.offset` are kept tight to the expression and never
+                // moved to the next line, unlike the high-level member access
+                expr.visit(self)?;
+                write!(self.buf(), ".{}", ident.name)?;
+                Ok(())
             }
             YulExpression::Variable(ident) => {
                 write_chunk!(self, ident.loc.start(), ident.loc.end(), "{}", ident.name)
@@ -3196,9 +3879,24 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
     fn visit_yul_for(&mut self, stmt: &mut YulFor) -> Result<(), Self::Error> {
         return_source_if_disabled!(self, stmt.loc);
         write_chunk!(self, stmt.loc.start(), "for")?;
-        self.visit_yul_block(stmt.init_block.loc, &mut stmt.init_block.statements, true)?;
+
+        // empty init and post blocks are written as `{ }` to keep the header readable
+        let visit_header_block = |fmt: &mut Self, block: &mut YulBlock| -> Result<()> {
+            let has_comments_inside = fmt
+                .comments
+                .iter()
+                .next()
+                .map_or(false, |comment| comment.loc.end() < block.loc.end());
+            if block.statements.is_empty() && !has_comments_inside {
+                write_chunk!(fmt, block.loc.start(), block.loc.end(), "{{ }}")?;
+                return Ok(())
+            }
+            fmt.visit_yul_block(block.loc, &mut block.statements, true)
+        };
+
+        visit_header_block(self, &mut stmt.init_block)?;
         stmt.condition.visit(self)?;
-        self.visit_yul_block(stmt.post_block.loc, &mut stmt.post_block.statements, true)?;
+        visit_header_block(self, &mut stmt.post_block)?;
         self.visit_yul_block(stmt.execution_block.loc, &mut stmt.execution_block.statements, true)?;
         Ok(())
     }
@@ -3493,4 +4191,642 @@ mod tests {
     test_directory! { TrailingComma }
     test_directory! { SelectorOverride }
     test_directory! { PragmaDirective }
+    test_directory! { TupleExpression }
+    test_directory! { DeprecatedStatements }
+    test_directory! { StatementBraces }
+    test_directory! { DataLocation }
+    test_directory! { MappingType }
+    test_directory! { FunctionAttributesMultiline }
+    test_directory! { FreeFunctions }
+    test_directory! { SourceUnitHeader }
+    test_directory! { SingleLineFunctionBody }
+    test_directory! { NatSpecStyle }
+    test_directory! { BlankLines }
+    test_directory! { MetaTypeExpression }
+    test_directory! { EmptyBrackets }
+    test_directory! { DeleteExpression }
+    test_directory! { AssemblyFlags }
+    test_directory! { SpdxLicense }
+    test_directory! { MemberAccess }
+    test_directory! { IndexAccess }
+    test_directory! { AddressCast }
+    test_directory! { AlignAssignments }
+    test_directory! { Identifiers }
+    test_directory! { EmitStatement }
+    test_directory! { EmptyBlocks }
+    test_directory! { AssemblyPassthrough }
+    test_directory! { SortContractMembers }
+
+    fn test_whitespace_independence(sources: &[&str]) {
+        let formatted = sources.iter().map(|source| crate::fmt(source).unwrap()).collect_vec();
+        for output in formatted.iter().skip(1) {
+            pretty_assertions::assert_eq!(&formatted[0], output);
+        }
+    }
+
+    macro_rules! test_whitespace_independence {
+        ($name:ident, $($source:expr),+ $(,)?) => {
+            #[test]
+            fn $name() {
+                test_whitespace_independence(&[$($source),+]);
+            }
+        };
+    }
+
+    test_whitespace_independence! {
+        whitespace_independent_contract,
+        r#"contract A is B, C {
+    event Transfer(address indexed from, address indexed to, uint256 value);
+
+    mapping(address => uint256) public balances;
+
+    function transfer(address to, uint256 value) external returns (bool) {
+        balances[msg.sender] -= value;
+        balances[to] += value;
+        emit Transfer(msg.sender, to, value);
+        return true;
+    }
+}
+"#,
+        r#"contract   A   is   B ,C{
+    event   Transfer( address  indexed from ,address indexed  to,uint256 value ) ;
+
+    mapping( address=>uint256 )   public   balances ;
+
+    function   transfer( address to ,uint256  value )external   returns( bool ){
+        balances[ msg.sender ]-=value;
+        balances [to]  +=  value ;
+        emit   Transfer( msg.sender,to ,value );
+        return   true ;
+    }
+}
+"#,
+        r#"contract A
+    is B,
+    C
+{
+    event Transfer(
+        address indexed from,
+        address indexed to,
+        uint256 value
+    );
+
+    mapping(address
+        => uint256) public
+        balances;
+
+    function transfer(
+        address to,
+        uint256 value
+    ) external
+        returns (bool)
+    {
+        balances[msg.sender]
+            -= value;
+        balances[to] +=
+            value;
+        emit Transfer(
+            msg.sender, to, value);
+        return
+            true;
+    }
+}
+"#,
+    }
+
+    test_whitespace_independence! {
+        whitespace_independent_new_and_delete,
+        r#"contract A {
+    function f(uint256 length) external {
+        B b = new B(length);
+        uint256[] memory values = new uint256[](length);
+        delete values[0];
+    }
+}
+"#,
+        r#"contract A {
+    function f(uint256 length) external {
+        B b = new   B( length );
+        uint256[] memory values = new  uint256 [ ]( length );
+        delete   values[ 0 ];
+    }
+}
+"#,
+        r#"contract A {
+    function f(uint256 length) external {
+        B b = new
+            B(length);
+        uint256[] memory values = new
+            uint256[](length);
+        delete
+            values[0];
+    }
+}
+"#,
+    }
+
+    #[test]
+    fn nested_blocks() {
+        let source = r#"contract A {
+    function f() {
+        {
+            uint256 a;
+
+
+            {
+                a = 1;
+            }
+        }
+        unchecked {
+            {
+                a += 1;
+            }
+
+        }
+    }
+}
+"#;
+        let expected = r#"contract A {
+    function f() {
+        {
+            uint256 a;
+
+            {
+                a = 1;
+            }
+        }
+        unchecked {
+            {
+                a += 1;
+            }
+        }
+    }
+}
+"#;
+        pretty_assertions::assert_eq!(crate::fmt(source).unwrap(), expected);
+    }
+
+    #[test]
+    fn nested_control_flow() {
+        let source = r#"contract A {
+function f(uint256[] memory xs) {
+for (uint256 i; i < xs.length; i++) {
+if (xs[i] == 0) {
+emit Zero(i);
+} else if (xs[i] == 1) {
+revert One(i);
+} else {
+return;
+}
+}
+}
+}
+"#;
+        let expected = r#"contract A {
+  function f(uint256[] memory xs) {
+    for (uint256 i; i < xs.length; i++) {
+      if (xs[i] == 0) {
+        emit Zero(i);
+      } else if (xs[i] == 1) {
+        revert One(i);
+      } else {
+        return;
+      }
+    }
+  }
+}
+"#;
+        let parsed = parse(source).unwrap();
+        let mut output = String::new();
+        format(&mut output, parsed, FormatterConfig { tab_width: 2, ..Default::default() })
+            .unwrap();
+        pretty_assertions::assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn mixed_statements() {
+        let source = r#"function f(uint256[] memory values) returns (uint256 sum) {
+for (uint256 i; i < values.length; i++) {
+sum += values[i];
+}
+uint256 j = sum;
+while (j > 10) {
+j--;
+}
+do {
+j++;
+} while (j < 5);
+unchecked {
+sum += j;
+}
+assembly {
+sum := add(sum, 1)
+}
+try this.g(sum) returns (uint256 r) {
+sum = r;
+} catch {}
+if (sum == 0) {
+revert();
+}
+emit Sum(sum);
+return sum;
+}
+"#;
+        let expected = r#"function f(uint256[] memory values) returns (uint256 sum) {
+    for (uint256 i; i < values.length; i++) {
+        sum += values[i];
+    }
+    uint256 j = sum;
+    while (j > 10) {
+        j--;
+    }
+    do {
+        j++;
+    } while (j < 5);
+    unchecked {
+        sum += j;
+    }
+    assembly {
+        sum := add(sum, 1)
+    }
+    try this.g(sum) returns (uint256 r) {
+        sum = r;
+    } catch {}
+    if (sum == 0) {
+        revert();
+    }
+    emit Sum(sum);
+    return sum;
+}
+"#;
+        let mut output = String::new();
+        format(&mut output, parse(source).unwrap(), FormatterConfig::default()).unwrap();
+        pretty_assertions::assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn override_forms() {
+        let source = r#"contract A is B, C {
+    function a() public override {}
+    function b() public override(B) {}
+    function c() public override( B,C ) {}
+}
+"#;
+        let expected = r#"contract A is B, C {
+    function a() public override {}
+    function b() public override (B) {}
+    function c() public override (B, C) {}
+}
+"#;
+        pretty_assertions::assert_eq!(crate::fmt(source).unwrap(), expected);
+    }
+
+    #[test]
+    fn only_returns_overflow() {
+        let source = r#"contract A {
+    function f(uint256 a) external returns (uint256 someVeryLongReturnValueName, uint256 anotherLongReturnValue) {
+        a = 1;
+    }
+}
+"#;
+        // the parameters fit on the header line, so only the returns clause is wrapped
+        let expected = r#"contract A {
+    function f(uint256 a)
+        external
+        returns (
+            uint256 someVeryLongReturnValueName,
+            uint256 anotherLongReturnValue
+        )
+    {
+        a = 1;
+    }
+}
+"#;
+        let parsed = parse(source).unwrap();
+        let mut output = String::new();
+        format(&mut output, parsed, FormatterConfig { line_length: 80, ..Default::default() })
+            .unwrap();
+        pretty_assertions::assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn bodyless_virtual_function() {
+        let source = r#"abstract contract A {
+    function foo() virtual external returns(uint) ;
+}
+"#;
+        let expected = r#"abstract contract A {
+    function foo() external virtual returns (uint256);
+}
+"#;
+        pretty_assertions::assert_eq!(crate::fmt(source).unwrap(), expected);
+    }
+
+    #[test]
+    fn modifier_with_nested_call_argument() {
+        let source = r#"contract A {
+    function f() external onlyRole( keccak256( 'ADMIN_ROLE' ) ) {}
+}
+"#;
+        let expected = r#"contract A {
+    function f() external onlyRole(keccak256("ADMIN_ROLE")) {}
+}
+"#;
+        pretty_assertions::assert_eq!(crate::fmt(source).unwrap(), expected);
+    }
+
+    #[test]
+    fn omit_default_visibility() {
+        let source = r#"contract A {
+    function f() public returns (uint256) {
+        return 1;
+    }
+}
+"#;
+        let format_with = |compiler_version: Option<&str>| {
+            let config = FormatterConfig {
+                omit_default_visibility: true,
+                compiler_version: compiler_version.map(|v| Version::parse(v).unwrap()),
+                ..Default::default()
+            };
+            let mut output = String::new();
+            format(&mut output, parse(source).unwrap(), config).unwrap();
+            output
+        };
+
+        pretty_assertions::assert_eq!(
+            format_with(Some("0.4.24")),
+            source.replace("f() public returns", "f() returns")
+        );
+        pretty_assertions::assert_eq!(format_with(Some("0.5.0")), source);
+        pretty_assertions::assert_eq!(format_with(None), source);
+    }
+
+    #[test]
+    fn explicit_default_visibility() {
+        let source = r#"contract A {
+    function f() returns (uint256) {
+        return 1;
+    }
+
+    function g() external {}
+}
+"#;
+        let format_with = |compiler_version: Option<&str>| {
+            let config = FormatterConfig {
+                explicit_default_visibility: true,
+                compiler_version: compiler_version.map(|v| Version::parse(v).unwrap()),
+                ..Default::default()
+            };
+            let mut output = String::new();
+            format(&mut output, parse(source).unwrap(), config).unwrap();
+            output
+        };
+
+        pretty_assertions::assert_eq!(
+            format_with(Some("0.4.24")),
+            source.replace("f() returns", "f() public returns")
+        );
+        pretty_assertions::assert_eq!(format_with(Some("0.5.0")), source);
+        pretty_assertions::assert_eq!(format_with(None), source);
+
+        let config = FormatterConfig {
+            omit_default_visibility: true,
+            explicit_default_visibility: true,
+            ..Default::default()
+        };
+        assert!(format(&mut String::new(), parse(source).unwrap(), config).is_err());
+    }
+
+    #[test]
+    fn constructor_visibility() {
+        let source = r#"contract A {
+    constructor() public payable {}
+}
+
+contract B {
+    constructor() internal {}
+}
+"#;
+        let format_with = |compiler_version: Option<&str>| {
+            let config = FormatterConfig {
+                compiler_version: compiler_version.map(|v| Version::parse(v).unwrap()),
+                ..Default::default()
+            };
+            let mut output = String::new();
+            format(&mut output, parse(source).unwrap(), config).unwrap();
+            output
+        };
+
+        pretty_assertions::assert_eq!(
+            format_with(Some("0.7.0")),
+            source.replace("() public payable", "() payable").replace("() internal", "()")
+        );
+        pretty_assertions::assert_eq!(format_with(Some("0.6.12")), source);
+        pretty_assertions::assert_eq!(format_with(None), source);
+    }
+
+    #[test]
+    fn import_groups() {
+        let source = r#"pragma solidity ^0.8.0;
+
+import "./Local.sol";
+import {Test} from "forge-std/Test.sol";
+// ERC20 implementation
+import {ERC20} from "@openzeppelin/token/ERC20.sol";
+import "forge-std/console.sol"; // logging
+import {Ownable} from "@openzeppelin/access/Ownable.sol";
+import "../Base.sol";
+
+contract A {}
+"#;
+        let expected = r#"pragma solidity ^0.8.0;
+
+import {Test} from "forge-std/Test.sol";
+import "forge-std/console.sol"; // logging
+
+import {Ownable} from "@openzeppelin/access/Ownable.sol";
+// ERC20 implementation
+import {ERC20} from "@openzeppelin/token/ERC20.sol";
+
+import "../Base.sol";
+import "./Local.sol";
+
+contract A {}
+"#;
+        let config = FormatterConfig {
+            import_groups: vec!["forge-std/".to_string(), "@openzeppelin/".to_string()],
+            ..Default::default()
+        };
+
+        let mut output = String::new();
+        format(&mut output, parse(source).unwrap(), config.clone()).unwrap();
+        pretty_assertions::assert_eq!(output, expected);
+
+        let mut output = String::new();
+        format(&mut output, parse(expected).unwrap(), config).unwrap();
+        pretty_assertions::assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn mutability_position() {
+        let source = r#"contract A {
+    function f() view public returns (uint256) {}
+    function g() external pure pure {}
+    function h() payable external {}
+}
+"#;
+        let expected = r#"contract A {
+    function f() public view returns (uint256) {}
+    function g() external pure {}
+    function h() external payable {}
+}
+"#;
+        let mut output = String::new();
+        format(&mut output, parse(source).unwrap(), FormatterConfig::default()).unwrap();
+        pretty_assertions::assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn format_with_level() {
+        let source = "function f(uint256 a) pure returns (uint256) {\nreturn a;\n}\n";
+        let mut parsed = parse(source).unwrap();
+
+        let mut output = String::new();
+        let mut formatter = Formatter::with_level(
+            &mut output,
+            parsed.src,
+            parsed.comments,
+            parsed.inline_config,
+            FormatterConfig::default(),
+            2,
+        );
+        parsed.pt.0.first_mut().unwrap().visit(&mut formatter).unwrap();
+
+        assert_eq!(
+            output,
+            "        function f(uint256 a) pure returns (uint256) {\n            return a;\n        }"
+        );
+    }
+
+    #[test]
+    fn no_stacked_blank_lines() {
+        let source = r#"pragma solidity ^0.8.0;
+
+
+
+import "forge-std/Test.sol";
+
+import "./Local.sol";
+
+
+/// @notice A documented contract
+
+
+contract A {
+
+
+    /// @dev first
+    uint256 a;
+
+
+
+    /// @dev second
+    function f() external {}
+
+
+}
+
+
+// trailing
+"#;
+        for import_groups in [vec![], vec!["forge-std/".to_string()]] {
+            for max_blank_lines in [0, 1] {
+                let config = FormatterConfig {
+                    import_groups: import_groups.clone(),
+                    max_blank_lines,
+                    ..Default::default()
+                };
+                let mut output = String::new();
+                format(&mut output, parse(source).unwrap(), config).unwrap();
+                assert!(!output.contains("\n\n\n"), "{output}");
+            }
+        }
+    }
+
+    // Reordering members changes the order of the contract parts, so this can't be a snapshot:
+    // the snapshot harness requires the formatted source to be AST-equal to the original.
+    #[test]
+    fn sort_contract_members() {
+        let source = include_str!("../testdata/SortContractMembers/original.sol");
+        let config = FormatterConfig { sort_contract_members: true, ..Default::default() };
+
+        let mut output = String::new();
+        format(&mut output, parse(source).unwrap(), config).unwrap();
+
+        assert_eq!(
+            output,
+            r#"contract SortContractMembers {
+    struct Item {
+        uint256 value;
+    }
+
+    /// @notice The owner of the contract
+    address public owner; // set in the constructor
+    uint256 value;
+
+    event Transfer(address from, address to);
+
+    error Unauthorized();
+
+    modifier onlyOwner() {
+        if (msg.sender != owner) revert Unauthorized();
+        _;
+    }
+
+    constructor() {
+        owner = msg.sender;
+    }
+
+    receive() external payable {}
+
+    function transfer(address to) external onlyOwner {
+        // notify the listeners
+        emit Transfer(msg.sender, to);
+    }
+
+    // the end of the contract
+}
+"#
+        );
+    }
+
+    #[test]
+    fn report_unsupported_nodes() {
+        let source = r#"contract A {
+    function (uint256) external returns (uint256) f;
+    uint256 a;
+}
+"#;
+        let mut parsed = parse(source).unwrap();
+
+        let mut output = String::new();
+        let mut formatter = Formatter::new(
+            &mut output,
+            parsed.src,
+            parsed.comments,
+            parsed.inline_config,
+            FormatterConfig::default(),
+        );
+        parsed.pt.visit(&mut formatter).unwrap();
+
+        let start = source.find("function").unwrap();
+        let end = source.find(" f;").unwrap();
+        assert_eq!(
+            formatter.unsupported_nodes(),
+            [UnsupportedNode { loc: Loc::File(0, start, end), kind: "function type" }]
+        );
+        drop(formatter);
+        assert!(output.contains("function (uint256) external returns (uint256) f;"), "{output}");
+    }
 }
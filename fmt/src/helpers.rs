@@ -1,9 +1,18 @@
 use crate::{
-    inline_config::{InlineConfig, InvalidInlineConfigItem},
-    Comments, Formatter, FormatterConfig, FormatterError, Visitable,
+    comments::CommentStringExt,
+    inline_config::{InlineConfig, InlineConfigItem, InvalidInlineConfigItem},
+    solang_ext::LineOfCode,
+    Comments, Formatter, FormatterConfig, FormatterError, LineEnding, UnsupportedNode, Visitable,
 };
 use itertools::Itertools;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use solang_parser::pt::*;
+use std::{
+    io::{Read, Write},
+    ops::Range,
+    path::PathBuf,
+};
 
 /// Result of parsing the source code
 #[derive(Debug)]
@@ -30,27 +39,389 @@ pub fn parse(src: &str) -> Result<Parsed, Vec<solang_parser::diagnostics::Diagno
     Ok(Parsed { src, pt, comments, inline_config, invalid_inline_config_items })
 }
 
-/// Format parsed code
+/// Format parsed code. Returns the nodes the formatter doesn't support, which are written as is
 pub fn format<W: std::fmt::Write>(
     writer: &mut W,
     mut parsed: Parsed,
     config: FormatterConfig,
-) -> Result<(), FormatterError> {
-    let mut formatter =
-        Formatter::new(writer, parsed.src, parsed.comments, parsed.inline_config, config);
-    parsed.pt.visit(&mut formatter)
+) -> Result<Vec<UnsupportedNode>, FormatterError> {
+    format_parts(writer, parsed.src, &mut parsed.pt, parsed.comments, parsed.inline_config, config)
+}
+
+/// Format a source unit which was already parsed, e.g. with [`solang_parser::parse`]. The `src`
+/// and the `comments` must be the ones the source unit was parsed from
+pub fn format_source_unit(
+    src: &str,
+    source_unit: &mut SourceUnit,
+    comments: Vec<Comment>,
+    config: FormatterConfig,
+) -> Result<String, FormatterError> {
+    let comments = Comments::new(comments, src);
+    let inline_config_items = comments.parse_inline_config_items().filter_map(Result::ok);
+    let inline_config = InlineConfig::new(inline_config_items, src);
+
+    let mut output = String::new();
+    format_parts(&mut output, src, source_unit, comments, inline_config, config)?;
+
+    Ok(output)
+}
+
+/// Format the parts of the parsed code. Returns the nodes the formatter doesn't support
+fn format_parts<W: std::fmt::Write>(
+    writer: &mut W,
+    src: &str,
+    source_unit: &mut SourceUnit,
+    comments: Comments,
+    inline_config: InlineConfig,
+    config: FormatterConfig,
+) -> Result<Vec<UnsupportedNode>, FormatterError> {
+    let config = resolve_config(src, config)?;
+
+    let crlf = is_crlf(src, &config);
+
+    let mut output = String::new();
+    let mut formatter = Formatter::new(&mut output, src, comments, inline_config, config);
+    source_unit.visit(&mut formatter)?;
+    let unsupported = formatter.unsupported_nodes().to_vec();
+
+    // the formatter always emits `\n`, but the source code copied as is may contain `\r\n`
+    let output = output.replace("\r\n", "\n");
+    if crlf {
+        writer.write_str(&output.replace('\n', "\r\n"))?;
+    } else {
+        writer.write_str(&output)?;
+    }
+    Ok(unsupported)
+}
+
+/// Whether the formatted code should use `\r\n` line endings
+fn is_crlf(src: &str, config: &FormatterConfig) -> bool {
+    match config.line_ending {
+        LineEnding::Lf => false,
+        LineEnding::Crlf => true,
+        LineEnding::Preserve => is_crlf_dominant(src),
+    }
+}
+
+/// Whether most of the lines in the source code end with `\r\n`
+fn is_crlf_dominant(src: &str) -> bool {
+    let crlf = src.matches("\r\n").count();
+    let lf = src.matches('\n').count() - crlf;
+    crlf > lf
+}
+
+/// Number of indented lines inspected by [`detect_tab_width`]
+const TAB_WIDTH_SAMPLE_LINES: usize = 20;
+
+/// Infer the number of spaces per indentation level from the first indented lines of the source
+/// code. Lines indented with tabs and block comment continuations (` * ...`) are not taken into
+/// account
+pub fn detect_tab_width(src: &str) -> Option<usize> {
+    src.lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('\t'))
+        .filter(|line| !line.trim_start().starts_with('*'))
+        .map(|line| line.len() - line.trim_start_matches(' ').len())
+        .filter(|indent| *indent > 0)
+        .take(TAB_WIDTH_SAMPLE_LINES)
+        .min()
 }
 
 /// Parse and format a string with default settings
 pub fn fmt(src: &str) -> Result<String, FormatterError> {
-    let parsed = parse(src).map_err(|_| FormatterError::Fmt(std::fmt::Error))?;
+    let (output, _) = fmt_with_config(src, FormatterConfig::default())?;
+    Ok(output)
+}
+
+/// Parse and format a string with the provided config. Returns the formatted code along with the
+/// nodes the formatter doesn't support
+fn fmt_with_config(
+    src: &str,
+    config: FormatterConfig,
+) -> Result<(String, Vec<UnsupportedNode>), FormatterError> {
+    let parsed = parse(src).map_err(|_| parse_error(src))?;
 
     let mut output = String::new();
-    format(&mut output, parsed, FormatterConfig::default())?;
+    let unsupported = format(&mut output, parsed, config)?;
+
+    Ok((output, unsupported))
+}
+
+/// Validate the config and apply the options which depend on the source code being formatted,
+/// e.g. `detect_tab_width`
+fn resolve_config(
+    src: &str,
+    mut config: FormatterConfig,
+) -> Result<FormatterConfig, FormatterError> {
+    config.validate().map_err(|err| FormatterError::Custom(err.into()))?;
+    if config.detect_tab_width {
+        if let Some(tab_width) = detect_tab_width(src) {
+            config.tab_width = tab_width;
+        }
+    }
+    Ok(config)
+}
+
+/// Error for the source code which failed to parse. Yul objects (`object "Name" { ... }`) are
+/// reported explicitly, since they can't be parsed as Solidity
+fn parse_error(src: &str) -> FormatterError {
+    let is_yul_object = src
+        .trim_comments()
+        .trim_start()
+        .strip_prefix("object")
+        .map_or(false, |rest| rest.trim_start().starts_with('"'));
+    if is_yul_object {
+        FormatterError::Custom("Yul objects are not supported".into())
+    } else {
+        FormatterError::Fmt(std::fmt::Error)
+    }
+}
+
+/// Parse and format a string with the provided config. Returns the formatted code along with
+/// whether it differs from the source code and the nodes the formatter doesn't support, which are
+/// written as is
+pub fn format_with_status(
+    src: &str,
+    config: FormatterConfig,
+) -> Result<(String, bool, Vec<UnsupportedNode>), FormatterError> {
+    let (output, unsupported) = fmt_with_config(src, config)?;
+    let changed = output != src;
+    Ok((output, changed, unsupported))
+}
+
+/// Read all of the source code from the `reader`, format it and write the result to the `writer`,
+/// e.g. to format the standard input. Empty input produces empty output
+pub fn format_reader<R: Read, W: Write>(
+    mut reader: R,
+    mut writer: W,
+    config: FormatterConfig,
+) -> Result<(), FormatterError> {
+    let mut src = String::new();
+    reader.read_to_string(&mut src).map_err(|err| FormatterError::Custom(Box::new(err)))?;
+    if src.trim().is_empty() {
+        return Ok(())
+    }
+
+    let (output, _) = fmt_with_config(&src, config)?;
+    writer.write_all(output.as_bytes()).map_err(|err| FormatterError::Custom(Box::new(err)))?;
+
+    Ok(())
+}
+
+/// Format only the parts of the source code overlapping the `start..end` byte range and splice
+/// them back into the source code, leaving everything else untouched. Parts that overlap the range
+/// only partially are formatted as a whole. Inside of contracts, only the overlapping contract
+/// parts are formatted, unless the range covers none of them
+pub fn format_range(
+    src: &str,
+    start: usize,
+    end: usize,
+    config: FormatterConfig,
+) -> Result<String, FormatterError> {
+    let (mut pt, comments) = solang_parser::parse(src, 0).map_err(|_| parse_error(src))?;
+    let inline_config_items = Comments::new(comments.clone(), src)
+        .parse_inline_config_items()
+        .filter_map(Result::ok)
+        .collect::<Vec<_>>();
+
+    let config = resolve_config(src, config)?;
+
+    let overlaps = |loc: Loc| loc.start() < end && start < loc.end();
+    let range_part = RangePart {
+        src,
+        comments: &comments,
+        inline_config_items: &inline_config_items,
+        config: &config,
+    };
+
+    let mut replacements = Vec::new();
+    for part in pt.0.iter_mut() {
+        let loc = part.loc();
+        if !overlaps(loc) {
+            continue
+        }
+
+        if let SourceUnitPart::ContractDefinition(contract) = part {
+            let context = (**contract).clone();
+            let contract_parts =
+                contract.parts.iter_mut().filter(|part| overlaps(part.loc())).collect_vec();
+            if !contract_parts.is_empty() {
+                for contract_part in contract_parts {
+                    replacements.push(range_part.format(contract_part.loc(), 1, |fmt| {
+                        fmt.visit_contract_part(context.clone(), contract_part)
+                    })?);
+                }
+                continue
+            }
+        }
+
+        replacements.push(range_part.format(loc, 0, |fmt| part.visit(fmt))?);
+    }
+
+    let mut output = String::with_capacity(src.len());
+    let mut last_byte_written = 0;
+    for (range, formatted) in replacements {
+        output.push_str(&src[last_byte_written..range.start]);
+        output.push_str(&formatted);
+        last_byte_written = range.end;
+    }
+    output.push_str(&src[last_byte_written..]);
 
     Ok(output)
 }
 
+/// Shared state for formatting the parts of the source code in [`format_range`]
+struct RangePart<'a> {
+    src: &'a str,
+    comments: &'a [Comment],
+    inline_config_items: &'a [(Loc, InlineConfigItem)],
+    config: &'a FormatterConfig,
+}
+
+impl<'a> RangePart<'a> {
+    /// Format a single part of the source code located at `loc` with the output indented by
+    /// `level` indentation levels. Returns the byte range of the source code to be replaced and
+    /// the formatted code
+    fn format(
+        &self,
+        loc: Loc,
+        level: usize,
+        visit: impl FnOnce(&mut Formatter<String>) -> Result<(), FormatterError>,
+    ) -> Result<(Range<usize>, String), FormatterError> {
+        // only the comments inside of the part are formatted along with it
+        let comments = self
+            .comments
+            .iter()
+            .filter(|comment| {
+                comment.loc().start() >= loc.start() && comment.loc().end() <= loc.end()
+            })
+            .cloned()
+            .collect();
+
+        let mut output = String::new();
+        let mut formatter = Formatter::with_level(
+            &mut output,
+            self.src,
+            Comments::new(comments, self.src),
+            InlineConfig::new(self.inline_config_items.iter().copied(), self.src),
+            self.config.clone(),
+            level,
+        );
+        visit(&mut formatter)?;
+
+        let mut output = output.replace("\r\n", "\n").trim_end().to_string();
+        if is_crlf(self.src, self.config) {
+            output = output.replace('\n', "\r\n");
+        }
+
+        // the location of some parts doesn't include the trailing semicolon
+        let mut end = loc.end();
+        if output.ends_with(';') {
+            let rest = &self.src[end..];
+            let trimmed = rest.trim_start();
+            if trimmed.starts_with(';') {
+                end += rest.len() - trimmed.len() + 1;
+            }
+        }
+
+        // replace the indentation of the part as well if it starts on its own line
+        let line_start = self.src[..loc.start()].rfind('\n').map_or(0, |idx| idx + 1);
+        if self.src[line_start..loc.start()].trim().is_empty() {
+            Ok((line_start..end, output))
+        } else {
+            Ok((loc.start()..end, output.trim_start().to_string()))
+        }
+    }
+}
+
+/// Read and format multiple files with the same config. The files are formatted in parallel if
+/// the `parallel` feature is enabled. A failure to read or format a file does not affect the
+/// other files of the batch
+pub fn format_paths(
+    paths: &[PathBuf],
+    config: FormatterConfig,
+) -> Vec<(PathBuf, Result<String, FormatterError>)> {
+    format_paths_with_status(paths, config)
+        .into_iter()
+        .map(|(path, result)| (path, result.map(|(formatted, _, _)| formatted)))
+        .collect()
+}
+
+/// Read and format multiple files like [`format_paths`], returning the results of
+/// [`format_with_status`] for the source code that was read
+#[allow(clippy::type_complexity)]
+fn format_paths_with_status(
+    paths: &[PathBuf],
+    config: FormatterConfig,
+) -> Vec<(PathBuf, Result<(String, bool, Vec<UnsupportedNode>), FormatterError>)> {
+    let format_path = |path: &PathBuf| {
+        let result = std::fs::read_to_string(path)
+            .map_err(|err| FormatterError::Custom(Box::new(err)))
+            .and_then(|src| format_with_status(&src, config.clone()));
+        (path.clone(), result)
+    };
+
+    #[cfg(feature = "parallel")]
+    let paths = paths.par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let paths = paths.iter();
+
+    paths.map(format_path).collect()
+}
+
+/// Formatting status of a single file in a [`FormatReport`]
+#[derive(Debug)]
+pub enum FileStatus {
+    /// Formatting changes the file
+    Changed,
+    /// The file is already formatted
+    Unchanged,
+    /// The file could not be read or formatted
+    Error(FormatterError),
+}
+
+/// Summary of formatting multiple files without writing the formatted code back
+#[derive(Debug, Default)]
+pub struct FormatReport {
+    /// Number of files which would be changed by formatting
+    pub changed: usize,
+    /// Number of files which are already formatted
+    pub unchanged: usize,
+    /// Number of files which could not be read or formatted
+    pub errored: usize,
+    /// Status of each file in the order of the input paths
+    pub files: Vec<(PathBuf, FileStatus)>,
+    /// Nodes the formatter doesn't support and keeps as is, for each file which has any
+    pub unsupported: Vec<(PathBuf, Vec<UnsupportedNode>)>,
+}
+
+/// Format multiple files like [`format_paths`] and report which of them would be changed by
+/// formatting. The files are left untouched
+pub fn format_report(paths: &[PathBuf], config: FormatterConfig) -> FormatReport {
+    let mut report = FormatReport::default();
+    for (path, result) in format_paths_with_status(paths, config) {
+        let status = match result {
+            Ok((_, changed, unsupported)) => {
+                if !unsupported.is_empty() {
+                    report.unsupported.push((path.clone(), unsupported));
+                }
+                if changed {
+                    report.changed += 1;
+                    FileStatus::Changed
+                } else {
+                    report.unchanged += 1;
+                    FileStatus::Unchanged
+                }
+            }
+            Err(err) => {
+                report.errored += 1;
+                FileStatus::Error(err)
+            }
+        };
+        report.files.push((path, status));
+    }
+    report
+}
+
 /// Converts the start offset of a `Loc` to `(line, col)`
 pub fn offset_to_line_column(content: &str, start: usize) -> (usize, usize) {
     debug_assert!(content.len() > start);
@@ -68,3 +439,214 @@ pub fn offset_to_line_column(content: &str, start: usize) -> (usize, usize) {
 
     unreachable!("content.len() > start")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn yul_object_is_rejected() {
+        let src = r#"object "Token" {
+    code {
+        datacopy(0, dataoffset("runtime"), datasize("runtime"))
+        return(0, datasize("runtime"))
+    }
+}
+"#;
+        assert!(parse(src).is_err());
+        assert_eq!(fmt(src).unwrap_err().to_string(), "Yul objects are not supported");
+        assert!(matches!(fmt("contract A {").unwrap_err(), FormatterError::Fmt(_)));
+    }
+
+    #[test]
+    fn uppercase_hex_prefix_is_rejected() {
+        let src = "contract A {\n    uint256 a = 0XFF;\n}\n";
+        assert!(parse(src).is_err());
+        assert!(fmt(src).is_err());
+        assert_eq!(fmt(&src.replace("0X", "0x")).unwrap(), src.replace("0X", "0x"));
+    }
+
+    #[test]
+    fn tab_width_detection() {
+        let two_spaces = r#"contract A {
+  /**
+   * @notice doc
+   */
+  function f() external {
+    if (true) {
+      return;
+    }
+  }
+}
+"#;
+        let four_spaces = r#"contract A {
+    function f() external {
+        if (true) {
+            return;
+        }
+    }
+}
+"#;
+
+        assert_eq!(detect_tab_width(two_spaces), Some(2));
+        assert_eq!(detect_tab_width(four_spaces), Some(4));
+        assert_eq!(detect_tab_width("contract A {}\n"), None);
+    }
+
+    #[test]
+    fn format_multiple_paths() {
+        let testdata = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("testdata");
+        let paths = vec![
+            testdata.join("ThisExpression").join("original.sol"),
+            testdata.join("ThisExpression").join("missing.sol"),
+            testdata.join("UnitExpression").join("original.sol"),
+        ];
+        let config = FormatterConfig { line_length: 80, ..Default::default() };
+
+        let results = format_paths(&paths, config);
+        assert_eq!(results.len(), paths.len());
+        for ((path, result), expected_path) in results.iter().zip(&paths) {
+            assert_eq!(path, expected_path);
+            if path.ends_with("missing.sol") {
+                assert!(result.is_err());
+            } else {
+                let expected = std::fs::read_to_string(path.with_file_name("fmt.sol")).unwrap();
+                assert_eq!(result.as_ref().unwrap(), &expected);
+            }
+        }
+    }
+
+    #[test]
+    fn format_range_of_contract_part() {
+        let src = r#"contract A {
+    uint256   a;
+
+    function f( ) external {
+        a=1;
+    }
+
+    function g( ) external {
+        a=2;
+    }
+}
+"#;
+        let start = src.find("function g").unwrap();
+        let end = src.find("a=2").unwrap();
+        let expected = src.replace(
+            "function g( ) external {\n        a=2;",
+            "function g() external {\n        a = 2;",
+        );
+
+        assert_eq!(format_range(src, start, end, FormatterConfig::default()).unwrap(), expected);
+    }
+
+    #[test]
+    fn format_range_of_source_unit_part() {
+        let src = r#"pragma   solidity ^0.8.0;
+
+struct   S {uint a;}
+
+contract  A {}
+"#;
+        let start = src.find("uint a").unwrap();
+        let expected = src.replace("struct   S {uint a;}", "struct S {\n    uint256 a;\n}");
+
+        assert_eq!(
+            format_range(src, start, start + 1, FormatterConfig::default()).unwrap(),
+            expected
+        );
+        assert_eq!(format_range(src, 0, 0, FormatterConfig::default()).unwrap(), src);
+    }
+
+    #[test]
+    fn format_parsed_source_unit() {
+        let src = "contract  A {\n    // comment\n    uint a;\n}\n";
+        let (mut source_unit, comments) = solang_parser::parse(src, 0).unwrap();
+
+        assert_eq!(
+            format_source_unit(src, &mut source_unit, comments, FormatterConfig::default())
+                .unwrap(),
+            fmt(src).unwrap()
+        );
+    }
+
+    #[test]
+    fn format_report_of_paths() {
+        let testdata = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("testdata");
+        let paths = vec![
+            testdata.join("ThisExpression").join("original.sol"),
+            testdata.join("ThisExpression").join("fmt.sol"),
+            testdata.join("ThisExpression").join("missing.sol"),
+        ];
+        let config = FormatterConfig { line_length: 80, ..Default::default() };
+
+        let report = format_report(&paths, config);
+        assert_eq!((report.changed, report.unchanged, report.errored), (1, 1, 1));
+        assert!(report.unsupported.is_empty());
+        assert!(matches!(report.files[0].1, FileStatus::Changed));
+        assert!(matches!(report.files[1].1, FileStatus::Unchanged));
+        assert!(matches!(report.files[2].1, FileStatus::Error(_)));
+    }
+
+    #[test]
+    fn format_from_reader() {
+        let mut output = Vec::new();
+        let reader = std::io::Cursor::new("contract A{uint256 a;}");
+        format_reader(reader, &mut output, FormatterConfig::default()).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "contract A {\n    uint256 a;\n}\n");
+
+        let mut output = Vec::new();
+        format_reader(std::io::Cursor::new(""), &mut output, FormatterConfig::default()).unwrap();
+        assert!(output.is_empty());
+
+        let mut output = Vec::new();
+        let reader = std::io::Cursor::new("contract A {");
+        assert!(format_reader(reader, &mut output, FormatterConfig::default()).is_err());
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn format_status() {
+        let (output, changed, unsupported) =
+            format_with_status("contract A{uint256 a;}", FormatterConfig::default()).unwrap();
+        assert_eq!(output, "contract A {\n    uint256 a;\n}\n");
+        assert!(changed);
+        assert!(unsupported.is_empty());
+
+        let (unchanged, changed, _) =
+            format_with_status(&output, FormatterConfig::default()).unwrap();
+        assert_eq!(unchanged, output);
+        assert!(!changed);
+
+        let src = "contract A {\n    function (uint256) external returns (uint256) f;\n}\n";
+        let (output, _, unsupported) = format_with_status(src, FormatterConfig::default()).unwrap();
+        assert_eq!(output, src);
+        assert_eq!(unsupported.len(), 1);
+        assert_eq!(unsupported[0].kind, "function type");
+
+        assert!(format_with_status("contract A {", FormatterConfig::default()).is_err());
+    }
+
+    fn format_with_line_ending(src: &str, line_ending: LineEnding) -> String {
+        let mut output = String::new();
+        let config = FormatterConfig { line_ending, ..Default::default() };
+        format(&mut output, parse(src).unwrap(), config).unwrap();
+        output
+    }
+
+    #[test]
+    fn line_ending() {
+        let lf = "contract A {\n    uint256 a;\n}\n";
+        let crlf = "contract A {\r\n    uint256 a;\r\n}\r\n";
+
+        assert_eq!(format_with_line_ending(crlf, LineEnding::Lf), lf);
+        assert_eq!(format_with_line_ending(lf, LineEnding::Crlf), crlf);
+        assert_eq!(format_with_line_ending(crlf, LineEnding::Preserve), crlf);
+        assert_eq!(format_with_line_ending(lf, LineEnding::Preserve), lf);
+
+        // the line endings are preserved by default
+        let mut output = String::new();
+        format(&mut output, parse(crlf).unwrap(), FormatterConfig::default()).unwrap();
+        assert_eq!(output, crlf);
+    }
+}
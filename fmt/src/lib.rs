@@ -10,11 +10,17 @@ mod macros;
 pub mod solang_ext;
 mod string;
 pub mod visit;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
-pub use foundry_config::fmt::*;
+pub use forge_fmt_config::*;
 
 pub use comments::Comments;
-pub use formatter::{Formatter, FormatterError};
-pub use helpers::{fmt, format, offset_to_line_column, parse, Parsed};
+pub use formatter::{Formatter, FormatterError, UnsupportedNode};
+pub use helpers::{
+    detect_tab_width, fmt, format, format_paths, format_range, format_reader, format_report,
+    format_source_unit, format_with_status, offset_to_line_column, parse, FileStatus,
+    FormatReport, Parsed,
+};
 pub use inline_config::InlineConfig;
 pub use visit::{Visitable, Visitor};
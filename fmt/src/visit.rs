@@ -4,7 +4,9 @@ use crate::solang_ext::*;
 use solang_parser::pt::*;
 
 /// A trait that is invoked while traversing the Solidity Parse Tree.
-/// Each method of the [Visitor] trait is a hook that can be potentially overridden.
+/// Each method of the [Visitor] trait is a hook that can be potentially overridden. By default,
+/// source units and contracts are traversed down to their parts, and the rest of the nodes are
+/// passed to [Visitor::visit_source], so implementors only need to override the hooks they need.
 ///
 /// Currently the main implementor of this trait is the [`Formatter`](crate::Formatter) struct.
 pub trait Visitor {
@@ -14,12 +16,18 @@ pub trait Visitor {
         Ok(())
     }
 
-    fn visit_source_unit(&mut self, _source_unit: &mut SourceUnit) -> Result<(), Self::Error> {
-        Ok(())
+    fn visit_source_unit(&mut self, source_unit: &mut SourceUnit) -> Result<(), Self::Error>
+    where
+        Self: Sized,
+    {
+        source_unit.0.visit(self)
     }
 
-    fn visit_contract(&mut self, _contract: &mut ContractDefinition) -> Result<(), Self::Error> {
-        Ok(())
+    fn visit_contract(&mut self, contract: &mut ContractDefinition) -> Result<(), Self::Error>
+    where
+        Self: Sized,
+    {
+        contract.parts.visit(self)
     }
 
     fn visit_pragma(
@@ -646,3 +654,75 @@ impl_visitable!(ErrorParameter, visit_error_parameter);
 impl_visitable!(IdentifierPath, visit_ident_path);
 impl_visitable!(YulExpression, visit_yul_expr);
 impl_visitable!(YulTypedIdentifier, visit_yul_typed_ident);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    /// A visitor which only collects the names of contracts
+    #[derive(Default)]
+    struct ContractNames(Vec<String>);
+
+    impl Visitor for ContractNames {
+        type Error = std::fmt::Error;
+
+        fn visit_contract(&mut self, contract: &mut ContractDefinition) -> Result<(), Self::Error> {
+            self.0.push(contract.name.name.clone());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn partial_visitor() {
+        let src = "pragma solidity ^0.8.0;\ncontract A {}\nfunction f() {}\ninterface B {}\n";
+        let mut parsed = parse(src).unwrap();
+
+        let mut visitor = ContractNames::default();
+        parsed.pt.visit(&mut visitor).unwrap();
+        assert_eq!(visitor.0, vec!["A", "B"]);
+    }
+
+    /// A visitor which collects the kinds of the expression statements in function bodies
+    #[derive(Default)]
+    struct ExpressionKinds(Vec<&'static str>);
+
+    impl Visitor for ExpressionKinds {
+        type Error = std::fmt::Error;
+
+        fn visit_function(&mut self, func: &mut FunctionDefinition) -> Result<(), Self::Error> {
+            func.body.visit(self)
+        }
+
+        fn visit_block(
+            &mut self,
+            _loc: Loc,
+            _unchecked: bool,
+            statements: &mut Vec<Statement>,
+        ) -> Result<(), Self::Error> {
+            statements.visit(self)
+        }
+
+        fn visit_expr(&mut self, _loc: Loc, expr: &mut Expression) -> Result<(), Self::Error> {
+            self.0.push(match expr {
+                Expression::Add(..) => "add",
+                Expression::FunctionCall(..) => "call",
+                Expression::Ternary(..) => "ternary",
+                Expression::MemberAccess(..) => "member",
+                Expression::NumberLiteral(..) => "number",
+                _ => "other",
+            });
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn expression_dispatch() {
+        let src = "function f() {\n    a + b;\n    g();\n    c ? d : e;\n    s.x;\n    1;\n}\n";
+        let mut parsed = parse(src).unwrap();
+
+        let mut visitor = ExpressionKinds::default();
+        parsed.pt.visit(&mut visitor).unwrap();
+        assert_eq!(visitor.0, vec!["add", "call", "ternary", "member", "number"]);
+    }
+}
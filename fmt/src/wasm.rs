@@ -0,0 +1,38 @@
+//! Formatting entry point for WASM targets, e.g. browser-based playgrounds.
+//!
+//! Everything here works on strings only, so it doesn't depend on the file system or threads
+
+use crate::{helpers, FormatterConfig};
+
+/// Format the source code with the config serialized to JSON. Config entries which are missing
+/// from the JSON object fall back to their default values. Errors are returned as strings
+pub fn format(source: &str, config_json: &str) -> Result<String, String> {
+    let config: FormatterConfig = serde_json::from_str(config_json).map_err(|e| e.to_string())?;
+    let parsed = helpers::parse(source).map_err(|diags| {
+        diags.into_iter().map(|diag| diag.message).collect::<Vec<_>>().join("\n")
+    })?;
+
+    let mut output = String::new();
+    helpers::format(&mut output, parsed, config).map_err(|err| err.to_string())?;
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_with_json_config() {
+        let source = "contract A { function f() public { g(); } }";
+        let formatted = format(source, r#"{ "tab_width": 2 }"#).unwrap();
+        assert_eq!(formatted, "contract A {\n  function f() public {\n    g();\n  }\n}\n");
+    }
+
+    #[test]
+    fn format_errors() {
+        assert!(format("contract A {", "{}").is_err());
+        assert!(format("contract A {}", r#"{ "tab_width": "two" }"#).is_err());
+        assert!(format("contract A {}", "1").is_err());
+    }
+}
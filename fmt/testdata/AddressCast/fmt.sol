@@ -0,0 +1,8 @@
+contract AddressCast {
+    function withdraw(uint256 amount) external {
+        payable(address(this)).transfer(amount);
+        payable(msg.sender).transfer(
+            amountThatIsWithdrawnByTheSenderOfTheTransaction
+        );
+    }
+}
@@ -0,0 +1,21 @@
+contract AlignAssignments {
+    function f(uint256 value) external {
+        x = 1;
+        abc = 2;
+        s.inner.value = value;
+
+        a = 1;
+        // comment breaks the run
+        bc = 2;
+        defg = 3;
+
+        total += value;
+        y = 4;
+        z = 5;
+        single = 6;
+        if (value > 0) {
+            p = 1;
+            pqr = 2;
+        }
+    }
+}
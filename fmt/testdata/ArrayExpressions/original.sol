@@ -5,6 +5,8 @@ contract ArrayExpressions {
 
         uint256 length = 10;
         uint[] memory sample2 = new uint[](length);
+        uint256 count = length*2;
+        address[] memory sample4 = new address [ ]( count );
 
         uint /* comment1 */ [] memory /* comment2 */ sample3 // comment3
         ;
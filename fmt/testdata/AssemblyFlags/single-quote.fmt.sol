@@ -0,0 +1,14 @@
+// config: quote_style = "single"
+contract AssemblyFlags {
+    function f() external {
+        assembly ('memory-safe') {
+            mstore(0x40, 0x80)
+        }
+
+        assembly 'evmasm' ('memory-safe') {}
+
+        assembly 'evmasm' {}
+
+        assembly ('memory-safe', 'custom') {}
+    }
+}
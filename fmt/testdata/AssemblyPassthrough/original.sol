@@ -0,0 +1,13 @@
+contract AssemblyPassthrough {
+    function f(uint256 x) external pure returns (uint256 r) {
+      assembly {
+          let   y := add(x,1)
+          // hand tuned
+          switch y
+          case 0 { r := 1 }
+          default {
+              r :=   mul(y, 2)
+          }
+      }
+    }
+}
@@ -0,0 +1,15 @@
+contract BlankLines {
+    function f() public {
+
+
+        uint256 a = 1;
+
+
+
+        uint256 b = 2;
+        uint256 c = 3;
+
+
+    }
+    function g() public {}
+}
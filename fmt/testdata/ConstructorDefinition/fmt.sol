@@ -40,3 +40,17 @@ contract Constructors is Ownable, Changeable {
         onlyOwner
     {}
 }
+
+contract InternalConstructor is Base {
+    constructor(uint256 a, uint256 b) internal Base(a, b) {}
+}
+
+contract Immutables {
+    address immutable owner;
+    uint256 immutable created;
+
+    constructor() {
+        owner = msg.sender;
+        created = block.timestamp;
+    }
+}
@@ -15,3 +15,17 @@ contract Constructors is Ownable, Changeable {
 
     constructor(variable1, variable2, variable3, variable4, variable5, variable6, variable7) public Changeable(variable1, variable2, variable3, variable4, variable5, variable6, variable7) Ownable() onlyOwner {}
 }
+
+contract InternalConstructor is Base {
+    constructor(uint256 a,  uint256 b) internal Base( a, b ) {}
+}
+
+contract Immutables {
+    address   immutable owner;
+    uint immutable   created;
+
+    constructor() {
+        owner=msg.sender;
+        created = block.timestamp;
+    }
+}
@@ -45,3 +45,23 @@ contract ERC20DecimalsMock is ERC20 {
         _decimals = decimals_;
     }
 }
+
+interface IERC20Metadata is
+    IERC20,
+    IERC20Permit,
+    IERC165,
+    IAccessControlEnumerable
+{
+    function decimals() external view returns (uint8);
+}
+
+contract EmptyWithBase is Base {}
+
+interface IEmpty is IBase1, IBase2 {}
+
+contract QualifiedBase is lib.Base, Other.Nested.Base(1) {}
+
+contract QualifiedBasesWrapped is
+    lib.SomeVeryLongBaseContractName,
+    lib.AnotherVeryLongBaseContractName
+{}
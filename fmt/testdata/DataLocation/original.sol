@@ -0,0 +1,10 @@
+contract DataLocation {
+    event Transfer(address  indexed   from, address indexed to, uint256 value);
+
+    function params(uint256[]   memory values, string    calldata name) external {}
+
+    function locals() internal {
+        Foo   storage foo = foos[0];
+        uint256[]  memory   copy = values;
+    }
+}
@@ -0,0 +1,17 @@
+contract DeleteExpression {
+    struct Nested {
+        uint256 field;
+    }
+
+    struct S {
+        Nested nested;
+    }
+
+    mapping(address => uint256) balances;
+    S s;
+
+    function clear(address user) public {
+        delete balances[user];
+        delete s.nested.field;
+    }
+}
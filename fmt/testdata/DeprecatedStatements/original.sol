@@ -0,0 +1,13 @@
+pragma solidity ^0.4.18;
+
+contract DeprecatedStatements {
+    function fail(bool condition) public {
+        if (!condition)   throw ;
+        throw;
+    }
+
+    function kill(address  owner) public {
+        suicide( owner );
+        selfdestruct(owner  );
+    }
+}
@@ -82,3 +82,22 @@ contract HelloWorld {
  * }
  */
 function freeFloatingMultilineIndent() {}
+
+/// @notice Emitted on transfers
+/// @param amount The transferred amount
+event Transferred(uint256 amount);
+
+/// @notice Thrown on failure
+error Failed();
+
+/// @notice A documented enum
+enum State {
+    Active,
+    Inactive
+}
+
+/// @notice A documented struct
+struct Documented {
+    /// @dev the stored value
+    uint256 value;
+}
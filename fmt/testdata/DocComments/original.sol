@@ -79,3 +79,19 @@ contract A {
 }
 */
 function freeFloatingMultilineIndent() {}
+
+    /// @notice Emitted on transfers
+  /// @param amount The transferred amount
+event Transferred(uint256 amount);
+
+/// @notice Thrown on failure
+error Failed();
+
+  /// @notice A documented enum
+enum State { Active, Inactive }
+
+    /// @notice A documented struct
+struct Documented {
+    /// @dev the stored value
+    uint value;
+}
@@ -0,0 +1,14 @@
+library Events {
+    event SomethingHappened(uint256 value);
+}
+
+contract EmitStatement {
+    event Transfer(address to, uint256 amount);
+
+    function f(uint256 x) external {
+        emit Events . SomethingHappened( x );
+        emit   Transfer(msg.sender,x);
+        emit Lib.SomethingHappened(x);
+        emit Events.SomethingHappened({ value : x });
+    }
+}
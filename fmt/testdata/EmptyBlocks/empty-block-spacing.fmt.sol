@@ -0,0 +1,15 @@
+// config: empty_block_spacing = true
+contract EmptyBlocks {
+    constructor() { }
+
+    function f(bool flag) external {
+        if (flag) { }
+        for (uint256 i; i < 10; i++) { }
+        while (flag) { }
+        unchecked { }
+    }
+
+    function g() internal { }
+}
+
+contract Empty { }
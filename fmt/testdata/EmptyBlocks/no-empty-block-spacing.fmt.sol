@@ -0,0 +1,16 @@
+// config: bracket_spacing = true
+// config: empty_block_spacing = false
+contract EmptyBlocks {
+    constructor() {}
+
+    function f(bool flag) external {
+        if (flag) {}
+        for (uint256 i; i < 10; i++) {}
+        while (flag) {}
+        unchecked {}
+    }
+
+    function g() internal {}
+}
+
+contract Empty {}
@@ -0,0 +1,14 @@
+contract EmptyBrackets {
+    uint256[ ] values;
+    address[ ][ ] nested;
+
+    constructor( ) {}
+
+    function f( ) public returns (uint256[ ] memory) {
+        g( );
+        uint256[] memory empty = new uint256[ ](0);
+        return empty;
+    }
+
+    function g() internal {}
+}
@@ -0,0 +1,17 @@
+// config: single_line_enums = true
+// config: bracket_spacing = true
+contract EnumDefinitions {
+    enum Empty { }
+    enum ActionChoices { GoLeft, GoRight, GoStraight, SitStill }
+    enum States {
+        State1,
+        State2,
+        State3,
+        State4,
+        State5,
+        State6,
+        State7,
+        State8,
+        State9
+    }
+}
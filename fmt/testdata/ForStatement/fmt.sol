@@ -20,18 +20,32 @@ contract ForStatement {
 
         for (type(uint256).min;;) {}
 
-        for (;;) {
-            "test";
-        }
+        for (;;) "test";
 
-        for (uint256 i4; i4 < 10; i4++) {
-            i4++;
-        }
+        for (uint256 i4; i4 < 10; i4++) i4++;
 
         for (uint256 i5;;) {
             for (uint256 i6 = 10; i6 > i5; i6--) {
                 i5++;
             }
         }
+
+        for (uint256 i7; i7 < 10; i7++) {
+            if (i7 == 5) {
+                continue;
+            }
+            if (i7 == 8) {
+                break;
+            }
+        }
+
+        uint256[] memory arr;
+        for (uint256 i8 = 0; i8 < arr.length; ++i8) {
+            arr[i8] = i8;
+        }
+
+        for (Item storage item = items[0]; item.value < 10; item.value++) {
+            item.value += 1;
+        }
     }
 }
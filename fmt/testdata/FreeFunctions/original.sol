@@ -0,0 +1,15 @@
+pragma solidity ^0.8.0;
+uint256   constant MAX_SUPPLY=1000;
+uint256 constant   MIN_SUPPLY = 1;
+uint256 constant MAX_UINT=2**256-1;
+bytes32 constant DOMAIN_SEPARATOR_TYPEHASH = 0x035aff83d86937d35b32e04f0ddc6ff469290eef2f1b692d8a815c89404d4749;
+error   Unauthorized(address caller);
+struct Point { uint256 x; uint256 y; }
+enum Status { Active, Paused }
+function   add(uint256 a,uint256 b) pure returns(uint256) { return a+b; }
+function sub(uint256 a, uint256 b) pure returns (uint256) {
+        return a - b;
+}
+contract Math {
+    function sum(uint256 a) external pure returns (uint256) { return add(a, MAX_SUPPLY); }
+}
@@ -0,0 +1,13 @@
+contract FunctionAttributesMultiline {
+    function transferOwnership(address newOwner)
+        public
+        virtual
+        override
+        onlyOwner
+        nonReentrant
+        whenNotPaused
+        returns (bool success)
+    {
+        success = true;
+    }
+}
@@ -0,0 +1,8 @@
+// config: functions_attribute_multiline = "wrap"
+contract FunctionAttributesMultiline {
+    function transferOwnership(address newOwner) public virtual override
+        onlyOwner nonReentrant whenNotPaused returns (bool success)
+    {
+        success = true;
+    }
+}
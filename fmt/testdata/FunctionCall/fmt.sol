@@ -20,6 +20,17 @@ contract FunctionCall {
             111111111111111111111111111111111111111111111111111117,
             1111111111111111111111111111111111111111111111111111117
         );
+        assert(condition);
+        require(condition, "FunctionCall: short message");
+        require(
+            someVeryLongConditionVariableName && anotherVeryLongConditionVarName,
+            "FunctionCall: the condition was not met by the caller"
+        );
+        require(condition, InsufficientBalance(1, msg.sender));
+        require(
+            someVeryLongConditionVariableName && anotherVeryLongConditionVarName,
+            InsufficientBalance(someVeryLongBalanceVariableName, msg.sender)
+        );
     }
 
     function bar(uint256, uint256) private pure {
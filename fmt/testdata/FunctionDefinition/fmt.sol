@@ -706,4 +706,8 @@ contract FunctionOverrides is
     {
         a = 1;
     }
+
+    function oneBase(uint256 x) override (FunctionInterfaces) {
+        a = 1;
+    }
 }
@@ -214,5 +214,9 @@ contract FunctionOverrides is FunctionInterfaces, FunctionDefinitions {
     function oneParam(uint256 x) override(FunctionInterfaces, FunctionDefinitions, SomeOtherFunctionContract, SomeImport.AndAnotherFunctionContract) {
         a = 1;
     }
+
+    function oneBase(uint256 x) override( FunctionInterfaces ) {
+        a = 1;
+    }
 }
 
@@ -0,0 +1,13 @@
+contract $Identifiers {
+    uint256   private _value;
+    uint256[50] private __gap;
+    address $owner;
+
+    function _set(uint256 $amount)internal returns(uint256 _$result) {
+        _value=$amount;
+        _$result = _value+$amount;
+        $owner   = msg.sender;
+    }
+
+    function $() external view returns (address) { return $owner; }
+}
@@ -0,0 +1,8 @@
+contract IndexAccess {
+    function f(uint256 i, address a, address b, bytes memory x) external {
+        uint256 v = arr[ i ];
+        uint256 w = allowances [a][ b ];
+        bytes32 h = hashes[keccak256( x )];
+        nested[i][ i+1 ] = v + w;
+    }
+}
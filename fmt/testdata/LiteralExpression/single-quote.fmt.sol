@@ -56,5 +56,8 @@ some words'; /* comment10 */
         0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2;
         // non checksummed address
         0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2;
+        // hex numbers which are not addresses
+        0x1234_5678_9AbC_dEf0_1234_5678_9AbC_dEf0;
+        0xABCDEF0123456789abcdef0123456789ABCDEF0123456789abcdef0123456789;
     }
 }
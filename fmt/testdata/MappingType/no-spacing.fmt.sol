@@ -0,0 +1,10 @@
+// config: mapping_arrow_spacing = false
+contract Mapping {
+    mapping(address=>uint256) public balances;
+    mapping(address=>mapping(address=>uint256)) public allowances;
+    mapping(uint8=>mapping(uint8=>mapping(uint8=>bool))) nested;
+    mapping(address=>
+        mapping(uint256=>mapping(address=>VeryLongStructureTypeName))) data;
+    mapping(address=>
+        mapping(uint256=>SomeVeryLongStructName)) public longNamedMapping;
+}
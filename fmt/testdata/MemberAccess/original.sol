@@ -0,0 +1,16 @@
+contract MemberAccess {
+    function f() external returns (uint256) {
+        uint256 a = s . inner . value;
+        address sender = msg .sender;
+        token. transfer(sender, a);
+        return a;
+    }
+
+    function builtins(C c) external view {
+        bytes4 selector = this . transfer . selector;
+        bytes4 typeSelector = IERC20 .transfer. selector;
+        address target = this.builtins .address;
+        bytes memory code = address(c) . code;
+        bytes32 codehash = address( c ).codehash;
+    }
+}
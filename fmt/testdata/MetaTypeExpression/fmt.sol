@@ -0,0 +1,12 @@
+contract MetaType {
+    uint256 constant MAX = type(uint256).max;
+    int8 constant MIN = type(int8).min;
+
+    function interfaceId() public pure returns (bytes4) {
+        return type(IERC20).interfaceId;
+    }
+
+    function code() public pure returns (bytes memory) {
+        return type(Token).creationCode;
+    }
+}
@@ -11,4 +11,11 @@ contract ModifierDefinitions {
         uint256 d
     ) {}
     modifier overridden() override (Base1, Base2) {}
+    modifier virtualModifier() virtual {}
+    modifier overriddenVirtual() virtual override {}
+
+    modifier onlyOwner() {
+        require(msg.sender == owner);
+        _;
+    }
 }
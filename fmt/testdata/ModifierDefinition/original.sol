@@ -6,4 +6,11 @@ contract ModifierDefinitions {
     modifier fourParams(uint a,uint b   ,uint c, uint d) {}
     modifier overridden (
     ) override ( Base1 , Base2) {}
+    modifier virtualModifier   () virtual {}
+    modifier overriddenVirtual() virtual  override {}
+
+    modifier onlyOwner() {
+        require(msg.sender == owner);
+        _ ;
+    }
 }
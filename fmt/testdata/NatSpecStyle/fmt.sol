@@ -0,0 +1,30 @@
+/// @title A contract
+/// @notice Multiple tags
+contract NatSpec {
+    /**
+     * @notice Returns the sum
+     * @param a The first value
+     * @param b The second value
+     */
+    function sum(uint256 a, uint256 b) external pure returns (uint256) {
+        return a + b;
+    }
+
+    /// @notice A single tag
+    function single() external {}
+
+    /**
+     * @dev A compact block
+     */
+    function compact() external {}
+
+    /// @dev First line of the description
+    ///      continues on a second line
+    function multiline() external {}
+
+    /**
+     * @dev Block description that
+     *      spans two lines
+     */
+    function multilineBlock() external {}
+}
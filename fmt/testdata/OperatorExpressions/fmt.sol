@@ -13,6 +13,15 @@ function test() {
     uint256 expr012 = ++expr011 + 1;
     bool expr013 = ++expr012 == expr011 - 1;
     bool expr014 = ++(++expr013)--;
+    bool expr015 = (a || b) && c;
+    bool expr016 = a || b && c;
+    bool expr017 = (a && b) || (c > d);
+    int256 expr018 = a - -b;
+    int256 expr019 = -x;
+    bool expr020 = !flag;
+    uint256 expr021 = ~mask;
+    i++;
+    --j;
     if (++batch.movesPerformed == drivers.length) createNewBatch();
     sum += getPrice(
         ACCELERATE_STARTING_PRICE,
@@ -40,4 +49,8 @@ function test() {
             || op == 0xFA // STATICCALL
             || op == 0xFF // SELFDESTRUCT
     ) return false;
+    uint256 total = firstValueInTheChain + secondValueInTheChain
+        + thirdValueInTheChain + fourthValueInTheChain + fifthValueInTheChain;
+    bool valid = isOwnerOfTheTokenId && isApprovedForAllTokens
+        || isOperatorOfTheVault && hasEnoughBalanceLeft;
 }
@@ -7,3 +7,5 @@ contract Contract {}
 pragma solidity 0.8.17;
 
 pragma experimental ABIEncoderV2;
+
+pragma custom someValue;
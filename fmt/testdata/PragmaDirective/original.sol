@@ -6,4 +6,5 @@ contract Contract {}
 // preserves lines
 pragma solidity 0.8.17;
 
-pragma experimental ABIEncoderV2;
\ No newline at end of file
+pragma experimental ABIEncoderV2;
+pragma   custom    someValue;
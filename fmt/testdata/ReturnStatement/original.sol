@@ -57,4 +57,14 @@ contract ReturnStatement {
 
         return someFunction().getValue().modifyValue().negate().scaleBySomeFactor(1000).transformToTuple();
     }
+
+    function returnNamed(uint256 val) external returns (uint256 a, bool b) {
+        if (val == 0) {
+            return ;
+        }
+
+        a = val;
+        b = true;
+        return ( a,b );
+    }
 }
@@ -1,5 +1,6 @@
 contract RevertStatement {
     error TestError(uint256, bool, string);
+    error CustomError();
 
     function someVeryLongFunctionNameToGetDynamicErrorMessageString()
         public
@@ -53,4 +54,10 @@ contract RevertStatement {
             someVeryLongFunctionNameToGetDynamicErrorMessageString() /* comment17 */
         );
     }
+
+    function revertForms() external {
+        revert();
+        revert("message");
+        revert CustomError();
+    }
 }
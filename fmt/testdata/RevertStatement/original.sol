@@ -1,5 +1,6 @@
 contract RevertStatement {
     error TestError(uint256,bool,string);
+    error CustomError();
 
     function someVeryLongFunctionNameToGetDynamicErrorMessageString() public returns (string memory) {
         return "";
@@ -41,4 +42,10 @@ contract RevertStatement {
 
         revert TestError ( /* comment16 */ 1, true, someVeryLongFunctionNameToGetDynamicErrorMessageString() /* comment17 */);
     }
+
+    function revertForms() external {
+        revert ( ) ;
+        revert ( "message" );
+        revert   CustomError ( );
+    }
 }
\ No newline at end of file
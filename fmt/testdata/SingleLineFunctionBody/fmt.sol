@@ -0,0 +1,21 @@
+contract Getters {
+    uint256 private _value;
+
+    function value() external view returns (uint256) {
+        return _value;
+    }
+
+    function valueWithComment() external view returns (uint256) {
+        // the stored value
+        return _value;
+    }
+
+    function twoStatements() external view returns (uint256) {
+        uint256 v = _value;
+        return v;
+    }
+
+    function veryLongFunctionName() external view returns (uint256) {
+        return _value + _value;
+    }
+}
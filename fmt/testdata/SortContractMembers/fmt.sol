@@ -0,0 +1,32 @@
+contract SortContractMembers {
+    function transfer(address to) external onlyOwner {
+        // notify the listeners
+        emit Transfer(msg.sender, to);
+    }
+
+    /// @notice The owner of the contract
+    address public owner; // set in the constructor
+
+    modifier onlyOwner() {
+        if (msg.sender != owner) revert Unauthorized();
+        _;
+    }
+
+    error Unauthorized();
+
+    event Transfer(address from, address to);
+
+    constructor() {
+        owner = msg.sender;
+    }
+
+    struct Item {
+        uint256 value;
+    }
+
+    receive() external payable {}
+
+    uint256 value;
+
+    // the end of the contract
+}
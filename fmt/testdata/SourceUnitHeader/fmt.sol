@@ -0,0 +1,6 @@
+pragma solidity ^0.8.0;
+
+import "A.sol";
+import {B} from "B.sol";
+
+contract C {}
@@ -0,0 +1,6 @@
+// config: blank_line_after_header = false
+pragma solidity ^0.8.0;
+
+import "A.sol";
+import {B} from "B.sol";
+contract C {}
@@ -0,0 +1,4 @@
+pragma solidity ^0.8.0;
+import "A.sol";
+import {B} from "B.sol";
+contract C {}
@@ -0,0 +1,4 @@
+pragma solidity ^0.8.0;
+// SPDX-License-Identifier: MIT
+
+contract A {}
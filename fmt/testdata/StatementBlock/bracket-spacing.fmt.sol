@@ -17,4 +17,29 @@ contract Contract {
 
         1 + 1;
     }
+
+    function empty() { }
+
+    function emptyBlocks() {
+        unchecked { }
+        if (true) {
+            // comment
+        }
+        for (uint256 i; i < 10; i++) {
+            /* comment */
+        }
+        while (true) { }
+    }
+
+    function onlyUnchecked(uint256 a) public pure returns (uint256) {
+        unchecked {
+            return a + 1;
+        }
+    }
+
+    function onlyAssembly() public {
+        assembly {
+            sstore(0, 1)
+        }
+    }
 }
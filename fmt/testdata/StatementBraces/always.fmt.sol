@@ -0,0 +1,31 @@
+// config: if_statement_braces = "always"
+// config: loop_braces = "always"
+contract StatementBraces {
+    function test(bool condition) external {
+        if (condition) {
+            execute();
+        }
+
+        if (condition) {
+            execute();
+        }
+
+        if (condition) {
+            execute();
+        } else {
+            executeElse();
+        }
+
+        while (condition) {
+            execute();
+        }
+
+        for (uint256 i; i < 10; ++i) {
+            values[i] = 0;
+        }
+
+        for (uint256 i; i < 10; ++i) {
+            values[i] = 0;
+        }
+    }
+}
@@ -0,0 +1,18 @@
+contract StatementBraces {
+    function test(bool condition) external {
+        if (condition) execute();
+
+        if (condition)
+            execute();
+
+        if (condition) execute();
+        else executeElse();
+
+        while (condition) execute();
+
+        for (uint256 i; i < 10; ++i) values[i] = 0;
+
+        for (uint256 i; i < 10; ++i)
+            values[i] = 0;
+    }
+}
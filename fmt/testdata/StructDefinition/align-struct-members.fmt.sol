@@ -0,0 +1,23 @@
+// config: align_struct_members = true
+struct Foo {}
+
+struct Bar {
+    uint256 foo;
+    string  bar;
+}
+
+struct MyStruct {
+    // first 1
+    // first 2
+    uint256 field1;
+    // second
+    uint256 field2;
+}
+
+struct Position {
+    address                  owner;
+    uint256                  amount;
+    // approvals
+    mapping(address => bool) approvals;
+    bytes32                  id;
+}
@@ -13,3 +13,11 @@ struct MyStruct {
     // second
     uint256 field2;
 }
+
+struct Position {
+    address owner;
+    uint256 amount;
+    // approvals
+    mapping(address => bool) approvals;
+    bytes32 id;
+}
@@ -8,3 +8,10 @@ struct MyStruct {
     // second
     uint256 field2;
 }
+
+struct Position {
+    address owner; uint amount;
+    // approvals
+    mapping(address=>bool) approvals;
+    bytes32   id;
+}
@@ -29,5 +29,9 @@ contract TernaryExpression {
             // comment10
             ? msg.value
             : parseAmount(IERC20(asset).balanceOf(msg.sender), msg.data);
+
+        condition ? 0 : otherCondition ? 1 : 2;
+
+        uint256 fee = amount > someVeryLongThresholdValue ? highFee : amount > mediumThreshold ? mediumFee : amount > lowThreshold ? lowFee : 0;
     }
 }
\ No newline at end of file
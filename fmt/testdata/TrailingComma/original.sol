@@ -3,6 +3,7 @@ contract C is Contract {
     // invalid solidity code, but valid pt
     modifier m2(uint256) returns (uint256,,,) {}
 
+    constructor(uint256 a, ,) {}
     function f(uint256 a, ) external {}
     function f2(uint256 a, , , ,bytes32 b) external returns (uint256,,,,) {}
 
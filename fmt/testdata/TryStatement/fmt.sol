@@ -70,5 +70,25 @@ contract TryStatement {
         } catch /* comment11 */ Panic(uint256) {
             unknown.handleError();
         } catch {}
+
+        try unknown.lookup() returns (uint256 value) {
+            unknown.doSomething();
+        } catch Error(string memory reason) {
+            unknown.handleError();
+        } catch Panic(uint256 errorCode) {
+            unknown.handleError();
+        } catch (bytes memory lowLevelData) {
+            unknown.handleError();
+        }
+    }
+
+    function create(bytes32 salt, uint256 a) external {
+        try new Factory(a) returns (Factory created) {
+            created;
+        } catch {}
+
+        try new Factory{salt: salt}(a) returns (Factory f) {} catch {}
+
+        try new Factory{value: msg.value}(a) returns (Factory f) {} catch {}
     }
 }
@@ -0,0 +1,22 @@
+contract TupleExpression {
+    uint256 firstTupleValueNumber;
+    uint256 secondTupleValueNumber;
+    uint256 thirdTupleValueNumber;
+    address target;
+    bytes data;
+
+    function getValues() internal pure returns (uint256, uint256, uint256) {
+        return (1, 2, 3);
+    }
+
+    function swap(uint256 a, uint256 b, uint256 c) external {
+        (a, b) = (b, a);
+        (a,b,c)=(c,  b,a);
+        ( , , uint256 third) = getValues();
+        (bool success, ) = target.call(data);
+        uint256 single = ( a );
+        uint256 product = ( a + b ) * c;
+        (uint256 x, uint256 y) = ( a,b );
+        (a, b, c) = (firstTupleValueNumber, secondTupleValueNumber, thirdTupleValueNumber);
+    }
+}
@@ -11,6 +11,9 @@ contract UnitExpression {
         value = 1 wei;
         value = 1 gwei;
         value = 1 ether;
+        value = 1e18 wei;
+        value = 0.5 ether;
+        timestamp = 2 days;
 
         uint256 someVeryVeryVeryLongVaribleNameForTheMultiplierForEtherValue;
 
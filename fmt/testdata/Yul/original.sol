@@ -114,6 +114,10 @@ contract Yul {
         assembly {
             for { let i := 0} lt(i, 10) { i := add(i, 1) } { mstore(i, 7) }
 
+            for {} lt(i, n) {} { i := add(i, 1) }
+
+            for { let j := 0 } lt(j, n) { j := add(j, 1) } { let v := mload(j) mstore(j, add(v, 1)) }
+
             function sample(x, y) -> someVeryLongVariableName, anotherVeryLongVariableNameToTriggerNewline {
                 someVeryLongVariableName := 0
                 anotherVeryLongVariableNameToTriggerNewline := 0
@@ -136,6 +140,29 @@ contract Yul {
             val1, val2, val3, val4, val5, val6, val7 := functionThatReturnsSevenValuesAndCanBeUsedInAssignment()
         }
 
+        assembly {
+            function divmod(a, b) -> q, r {
+                q := div(a,b)
+                r :=mod(a, b)
+            }
+
+            let   sum :=add( 1,2 )
+            let q,   r :=  divmod(sum,2)
+              let  product := mul( sum ,  q)
+        }
+
+        assembly {
+            switch   calldataload(4)
+            case 0x01 {    }
+                case 2 { sstore(0, 2) }
+            default {
+                revert(0, 0)
+            }
+        }
+
         assembly { a := 1 /* some really really really long comment that should not fit in one line */  }
+
+        assembly { sstore(x.slot, v) let o := x.offset
+            sstore(  someVeryLongStorageVariableName.slot, add(sload(someVeryLongStorageVariableName.slot), 1)) }
     }
 }
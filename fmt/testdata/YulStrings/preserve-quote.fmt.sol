@@ -10,6 +10,8 @@ contract Yul {
             let f := hex'deadbeef'
             let g := hex"deadbeef":u32
             let h := hex'deadbeef':u32
+            mstore(0, "abc")
+            mstore(32, 'a"b')
             datacopy(0, dataoffset('runtime'), datasize("runtime"))
             return(0, datasize("runtime"))
         }